@@ -0,0 +1,64 @@
+//! Generates a man page alongside the build so packagers can install it
+//! without a separate build step.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const MAN_PAGE: &str = r#".TH KVVLIVEAPI 1 "" "kvvliveapi" "User Commands"
+.SH NAME
+kvvliveapi \- query live KVV tram/bus departures from the command line
+.SH SYNOPSIS
+.B kvvliveapi
+.I search
+(\fINAME\fR|\fISTOP_ID\fR)
+.br
+.B kvvliveapi
+.I search
+\fILAT\fR \fILON\fR
+.br
+.B kvvliveapi
+.I departures
+\fISTOP_ID\fR [\fIROUTE\fR] [\fB\-\-accessible\fR] [\fB\-\-max\-dest\-len\fR \fIN\fR] [\fB\-\-format\fR plain\-speech]
+.br
+.B kvvliveapi
+.I luckysearch
+\fINAME\fR [\fB\-\-accessible\fR] [\fB\-\-max\-dest\-len\fR \fIN\fR] [\fB\-\-format\fR plain\-speech]
+.SH DESCRIPTION
+.B kvvliveapi
+is a thin command line wrapper around the live departure data API of the
+Karlsruher Verkehrsverbund (KVV).
+.SH OPTIONS
+.TP
+\fB\-\-accessible\fR
+Only show departures served by low-floor (wheelchair-accessible) vehicles.
+.TP
+\fB\-\-max\-dest\-len\fR \fIN\fR
+Shorten destination names to at most \fIN\fR characters.
+.TP
+\fB\-\-format\fR plain\-speech
+Print full sentences instead of a table, for screen readers and
+text-to-speech.
+.TP
+\fB\-\-lang\fR de|en
+Output language for generated strings. Defaults to the \fBLANG\fR
+environment variable.
+.SH EXIT STATUS
+.TP
+0
+Success.
+.TP
+1
+Usage error, or the request failed (network error, unknown stop, etc.).
+.SH FILES
+kvvliveapi currently reads no configuration file.
+.SH AUTHOR
+Clemens Wallrath <dev@nervengiftlabs.de>
+"#;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set by cargo");
+    let dest = Path::new(&out_dir).join("kvvliveapi.1");
+    fs::write(&dest, MAN_PAGE).expect("failed to write generated man page");
+    println!("cargo:warning=generated man page at {}", dest.display());
+}
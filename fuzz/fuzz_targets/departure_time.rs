@@ -0,0 +1,8 @@
+#![no_main]
+
+use kvvliveapi::parse_departure_time_str;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    let _ = parse_departure_time_str(s);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use kvvliveapi::Departures;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _: Result<Departures, _> = serde_json::from_slice(data);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use kvvliveapi::parse_timestamp_str;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    let _ = parse_timestamp_str(s);
+});
@@ -0,0 +1,111 @@
+//! Node.js bindings (napi-rs) exposing this crate's client, stops and
+//! departures as JS classes, so Electron-based info screens and existing
+//! JS dashboards can call this crate's battle-tested parsing directly
+//! instead of scraping the CLI's stdout. Kept as a separate crate — like
+//! `fuzz/`/`python/` — so the native-module toolchain never touches the
+//! plain Rust build.
+
+#[macro_use]
+extern crate napi_derive;
+
+use napi::bindgen_prelude::*;
+
+use ::kvvliveapi::client::{ClientError, KvvClient};
+
+fn to_napi_err(e: ClientError) -> Error {
+    Error::from_reason(e.to_string())
+}
+
+#[napi(object)]
+pub struct Stop {
+    pub name: String,
+    pub id: String,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl From<::kvvliveapi::Stop> for Stop {
+    fn from(s: ::kvvliveapi::Stop) -> Stop {
+        Stop { name: s.name, id: s.id, lat: s.lat, lon: s.lon }
+    }
+}
+
+/// A departure's destination, with any " über ..." via stops split out.
+#[napi(object)]
+pub struct Destination {
+    pub terminus: String,
+    pub via: Vec<String>,
+    pub raw: String,
+}
+
+impl From<::kvvliveapi::Destination> for Destination {
+    fn from(d: ::kvvliveapi::Destination) -> Destination {
+        Destination { terminus: d.terminus, via: d.via, raw: d.raw }
+    }
+}
+
+/// One scheduled departure. `time` is an ISO 8601 string (rather than a
+/// JS `Date`) so the original Europe/Berlin offset survives the trip
+/// across the native boundary unchanged.
+#[napi(object)]
+pub struct Departure {
+    pub route: String,
+    pub destination: Destination,
+    pub direction: String,
+    pub time: String,
+    pub lowfloor: bool,
+    pub realtime: bool,
+    pub traction: u32,
+}
+
+impl From<::kvvliveapi::Departure> for Departure {
+    fn from(d: ::kvvliveapi::Departure) -> Departure {
+        Departure {
+            route: d.route,
+            destination: d.destination.into(),
+            direction: d.direction,
+            time: d.time.to_rfc3339(),
+            lowfloor: d.lowfloor,
+            realtime: d.realtime,
+            traction: d.traction,
+        }
+    }
+}
+
+/// A client for the KVV (Karlsruhe) live data API.
+#[napi]
+pub struct Client {
+    inner: KvvClient,
+}
+
+#[napi]
+impl Client {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Client { inner: KvvClient::kvv() }
+    }
+
+    /// Search for stops by (partial) name.
+    #[napi]
+    pub fn search_by_name(&self, name: String) -> Result<Vec<Stop>> {
+        self.inner.search_by_name(&name).map(|stops| stops.into_iter().map(Stop::from).collect()).map_err(to_napi_err)
+    }
+
+    /// Search for the stop nearest to `(lat, lon)`.
+    #[napi]
+    pub fn search_by_latlon(&self, lat: f64, lon: f64) -> Result<Vec<Stop>> {
+        self.inner.search_by_latlon(lat, lon).map(|stops| stops.into_iter().map(Stop::from).collect()).map_err(to_napi_err)
+    }
+
+    /// Fetch the upcoming departures for a stop id.
+    #[napi]
+    pub fn departures_by_stop(&self, stop_id: String) -> Result<Vec<Departure>> {
+        self.inner.departures_by_stop(&stop_id).map(|d| d.departures.into_iter().map(Departure::from).collect()).map_err(to_napi_err)
+    }
+
+    /// Fetch the upcoming departures for one route at a stop id.
+    #[napi]
+    pub fn departures_by_route(&self, stop_id: String, route: String) -> Result<Vec<Departure>> {
+        self.inner.departures_by_route(&stop_id, &route).map(|d| d.departures.into_iter().map(Departure::from).collect()).map_err(to_napi_err)
+    }
+}
@@ -0,0 +1,130 @@
+//! PyO3 bindings exposing this crate's client, stops and departures as
+//! Python classes, packaged with maturin (see `pyproject.toml`). Kept as
+//! a separate crate — like `fuzz/` — so pulling in pyo3 and building an
+//! extension module never affects the plain Rust build.
+
+use chrono::DateTime;
+use chrono_tz::Tz;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+
+use ::kvvliveapi::client::{ClientError, KvvClient};
+
+fn to_py_err(e: ClientError) -> PyErr {
+    PyRuntimeError::new_err(e.to_string())
+}
+
+/// A KVV stop, as returned by [`Client.search_by_name`].
+#[pyclass(name = "Stop")]
+#[derive(Clone)]
+struct PyStop {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    id: String,
+    #[pyo3(get)]
+    lat: f64,
+    #[pyo3(get)]
+    lon: f64,
+}
+
+impl From<::kvvliveapi::Stop> for PyStop {
+    fn from(s: ::kvvliveapi::Stop) -> PyStop {
+        PyStop { name: s.name, id: s.id, lat: s.lat, lon: s.lon }
+    }
+}
+
+/// A departure's destination, with any " über ..." via stops split out.
+#[pyclass(name = "Destination")]
+#[derive(Clone)]
+struct PyDestination {
+    #[pyo3(get)]
+    terminus: String,
+    #[pyo3(get)]
+    via: Vec<String>,
+    #[pyo3(get)]
+    raw: String,
+}
+
+impl From<::kvvliveapi::Destination> for PyDestination {
+    fn from(d: ::kvvliveapi::Destination) -> PyDestination {
+        PyDestination { terminus: d.terminus, via: d.via, raw: d.raw }
+    }
+}
+
+/// One scheduled departure, as returned by [`Client.departures_by_stop`].
+#[pyclass(name = "Departure")]
+#[derive(Clone)]
+struct PyDeparture {
+    #[pyo3(get)]
+    route: String,
+    #[pyo3(get)]
+    destination: PyDestination,
+    #[pyo3(get)]
+    direction: String,
+    #[pyo3(get)]
+    time: DateTime<Tz>,
+    #[pyo3(get)]
+    lowfloor: bool,
+    #[pyo3(get)]
+    realtime: bool,
+    #[pyo3(get)]
+    traction: u32,
+}
+
+impl From<::kvvliveapi::Departure> for PyDeparture {
+    fn from(d: ::kvvliveapi::Departure) -> PyDeparture {
+        PyDeparture {
+            route: d.route,
+            destination: d.destination.into(),
+            direction: d.direction,
+            time: d.time,
+            lowfloor: d.lowfloor,
+            realtime: d.realtime,
+            traction: d.traction,
+        }
+    }
+}
+
+/// A client for the KVV (Karlsruhe) live data API.
+#[pyclass(name = "Client")]
+struct PyClient {
+    inner: KvvClient,
+}
+
+#[pymethods]
+impl PyClient {
+    #[new]
+    fn new() -> PyClient {
+        PyClient { inner: KvvClient::kvv() }
+    }
+
+    /// Search for stops by (partial) name.
+    fn search_by_name(&self, name: &str) -> PyResult<Vec<PyStop>> {
+        self.inner.search_by_name(name).map(|stops| stops.into_iter().map(PyStop::from).collect()).map_err(to_py_err)
+    }
+
+    /// Search for the stop nearest to `(lat, lon)`.
+    fn search_by_latlon(&self, lat: f64, lon: f64) -> PyResult<Vec<PyStop>> {
+        self.inner.search_by_latlon(lat, lon).map(|stops| stops.into_iter().map(PyStop::from).collect()).map_err(to_py_err)
+    }
+
+    /// Fetch the upcoming departures for a stop id.
+    fn departures_by_stop(&self, stop_id: &str) -> PyResult<Vec<PyDeparture>> {
+        self.inner.departures_by_stop(stop_id).map(|d| d.departures.into_iter().map(PyDeparture::from).collect()).map_err(to_py_err)
+    }
+
+    /// Fetch the upcoming departures for one route at a stop id.
+    fn departures_by_route(&self, stop_id: &str, route: &str) -> PyResult<Vec<PyDeparture>> {
+        self.inner.departures_by_route(stop_id, route).map(|d| d.departures.into_iter().map(PyDeparture::from).collect()).map_err(to_py_err)
+    }
+}
+
+#[pymodule]
+fn kvvliveapi(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyClient>()?;
+    m.add_class::<PyStop>()?;
+    m.add_class::<PyDestination>()?;
+    m.add_class::<PyDeparture>()?;
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! Destination shortening for narrow displays (status bars, LED matrices),
+//! usable from both library formatting code and the CLI.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Configurable engine that strips boilerplate, applies user-supplied
+/// replacement rules, and hard-truncates at a grapheme-cluster boundary so
+/// multi-byte characters are never cut in half.
+#[derive(Debug, Clone)]
+pub struct Abbreviator {
+    strip: Vec<String>,
+    replacements: Vec<(String, String)>,
+    max_len: Option<usize>,
+}
+
+impl Default for Abbreviator {
+    /// The default engine strips the redundant "Karlsruhe " prefix that
+    /// clutters most destinations and applies no truncation.
+    fn default() -> Self {
+        Abbreviator {
+            strip: vec!["Karlsruhe ".to_owned()],
+            replacements: Vec::new(),
+            max_len: None,
+        }
+    }
+}
+
+impl Abbreviator {
+    /// An engine that neither strips nor truncates anything.
+    pub fn none() -> Self {
+        Abbreviator { strip: Vec::new(), replacements: Vec::new(), max_len: None }
+    }
+
+    /// Remove every occurrence of `substr` from the input.
+    pub fn strip<S: Into<String>>(mut self, substr: S) -> Self {
+        self.strip.push(substr.into());
+        self
+    }
+
+    /// Replace every occurrence of `from` with `to`, applied after stripping.
+    pub fn replace<S: Into<String>, T: Into<String>>(mut self, from: S, to: T) -> Self {
+        self.replacements.push((from.into(), to.into()));
+        self
+    }
+
+    /// Hard-truncate the result to at most `len` grapheme clusters,
+    /// appending an ellipsis if anything was cut.
+    pub fn max_len(mut self, len: usize) -> Self {
+        self.max_len = Some(len);
+        self
+    }
+
+    /// Apply stripping, replacement, and truncation, in that order.
+    pub fn apply(&self, s: &str) -> String {
+        let mut result = s.to_owned();
+        for needle in &self.strip {
+            result = result.replace(needle.as_str(), "");
+        }
+        for (from, to) in &self.replacements {
+            result = result.replace(from.as_str(), to.as_str());
+        }
+        if let Some(max_len) = self.max_len {
+            result = truncate_graphemes(&result, max_len);
+        }
+        result
+    }
+}
+
+fn truncate_graphemes(s: &str, max_len: usize) -> String {
+    if s.graphemes(true).count() <= max_len {
+        return s.to_owned();
+    }
+    if max_len == 0 {
+        return String::new();
+    }
+    let keep = max_len.saturating_sub(1);
+    let mut truncated: String = s.graphemes(true).take(keep).collect();
+    truncated.push('\u{2026}');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_strips_karlsruhe_prefix_and_does_not_truncate() {
+        let abbreviator = Abbreviator::default();
+        assert_eq!(abbreviator.apply("Karlsruhe Marktplatz"), "Marktplatz");
+    }
+
+    #[test]
+    fn none_leaves_input_untouched() {
+        let abbreviator = Abbreviator::none();
+        assert_eq!(abbreviator.apply("Karlsruhe Marktplatz"), "Karlsruhe Marktplatz");
+    }
+
+    #[test]
+    fn strip_and_replace_apply_in_order() {
+        let abbreviator = Abbreviator::none().strip("Karlsruhe ").replace("Hauptbahnhof", "Hbf");
+        assert_eq!(abbreviator.apply("Karlsruhe Hauptbahnhof"), "Hbf");
+    }
+
+    #[test]
+    fn max_len_truncates_at_a_grapheme_boundary_with_ellipsis() {
+        let abbreviator = Abbreviator::none().max_len(5);
+        assert_eq!(abbreviator.apply("Hauptbahnhof"), "Haup\u{2026}");
+    }
+
+    #[test]
+    fn max_len_does_not_truncate_when_already_short_enough() {
+        let abbreviator = Abbreviator::none().max_len(5);
+        assert_eq!(abbreviator.apply("Hbf"), "Hbf");
+    }
+
+    #[test]
+    fn max_len_does_not_split_a_multi_byte_grapheme_cluster() {
+        // "Wabe" where each "e" is combined with a diaeresis as its own
+        // grapheme, to make sure truncation counts graphemes, not bytes.
+        let abbreviator = Abbreviator::none().max_len(3);
+        let result = abbreviator.apply("a\u{0308}b\u{0308}c\u{0308}d\u{0308}");
+        assert_eq!(result.graphemes(true).count(), 3);
+        assert!(result.ends_with('\u{2026}'));
+    }
+
+    #[test]
+    fn max_len_zero_truncates_to_empty() {
+        let abbreviator = Abbreviator::none().max_len(0);
+        assert_eq!(abbreviator.apply("Hauptbahnhof"), "");
+    }
+}
@@ -0,0 +1,89 @@
+//! Property-based generators for this crate's domain types, behind the
+//! `proptest` feature, so downstream code (and this crate's own
+//! formatting/diffing logic) can be checked against a wide range of
+//! realistic and edge-case data instead of only the handful of fixtures
+//! in the test suite.
+
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Europe::Berlin;
+use proptest::prelude::*;
+
+use {Departure, Departures, Destination, Occupancy, Stop};
+
+/// A short name-ish string: stop names, routes and via-stops are mostly
+/// letters, spaces and German umlauts, with the occasional period.
+fn name_string() -> impl Strategy<Value = String> {
+    "[A-Za-zÄÖÜäöüß. ]{1,24}"
+}
+
+fn datetime() -> impl Strategy<Value = DateTime<chrono_tz::Tz>> {
+    (0i64..2_000_000_000i64).prop_map(|epoch| Utc.timestamp_opt(epoch, 0).unwrap().with_timezone(&Berlin))
+}
+
+/// Raw departure-time strings exactly as the live API sends them,
+/// including the edge cases that have tripped up [`parse_departure_time`]
+/// before: the literal `"0"`, single-digit `"N min"`, and a bare 24-hour
+/// `"HH:MM"` clock time that may already be in the past (meaning
+/// tomorrow).
+pub fn raw_departure_time_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("0".to_owned()),
+        (1..=9i32).prop_map(|n| format!("{} min", n)),
+        (0..24u32, 0..60u32).prop_map(|(h, m)| format!("{:02}:{:02}", h, m)),
+    ]
+}
+
+pub fn stop() -> impl Strategy<Value = Stop> {
+    (name_string(), "[0-9]{1,3}", -90.0f64..90.0, -180.0f64..180.0)
+        .prop_map(|(name, suffix, lat, lon)| Stop { name, id: format!("de:8212:{}", suffix), lat, lon })
+}
+
+/// Platform/track labels as seen in the wild: a bare number, or a number
+/// with a letter suffix (e.g. a split platform) — or absent, since not
+/// every stop reports one.
+fn platform_string() -> impl Strategy<Value = Option<String>> {
+    prop::option::of("[0-9]{1,2}[A-Da-d]?")
+}
+
+fn occupancy() -> impl Strategy<Value = Option<Occupancy>> {
+    prop::option::of(prop_oneof![Just(Occupancy::Low), Just(Occupancy::Medium), Just(Occupancy::High)])
+}
+
+pub fn destination() -> impl Strategy<Value = Destination> {
+    (name_string(), prop::collection::vec(name_string(), 0..3)).prop_map(|(terminus, via)| {
+        let raw = if via.is_empty() { terminus.clone() } else { format!("{} über {}", terminus, via.join(", ")) };
+        Destination { terminus, via, raw }
+    })
+}
+
+pub fn departure() -> impl Strategy<Value = Departure> {
+    (
+        (
+            name_string(),
+            destination(),
+            prop_oneof![Just("1".to_owned()), Just("2".to_owned())],
+            datetime(),
+            any::<bool>(),
+            any::<bool>(),
+            0u32..3u32,
+        ),
+        platform_string(),
+        occupancy(),
+    )
+        .prop_map(|((route, destination, direction, time, lowfloor, realtime, traction), platform, occupancy)| Departure {
+            route,
+            destination,
+            direction,
+            time,
+            lowfloor,
+            realtime,
+            traction,
+            platform,
+            occupancy,
+        })
+}
+
+pub fn departures() -> impl Strategy<Value = Departures> {
+    (name_string(), datetime(), prop::collection::vec(departure(), 0..8))
+        .prop_map(|(stop_name, timestamp, departures)| Departures { stop_name, timestamp, departures })
+}
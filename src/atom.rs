@@ -0,0 +1,65 @@
+//! Atom feed generation, so boards (and eventually service alerts) can be
+//! subscribed to with an ordinary feed reader instead of custom polling
+//! code.
+//!
+//! This only builds the feed XML; serving it at `/feed/departures/{id}.atom`
+//! is the HTTP server's job once one exists, which it doesn't yet in this
+//! crate (see [`daemon`](::daemon) for the nearest thing, a control socket,
+//! not an HTTP listener).
+
+use std::fmt;
+use std::fmt::Write as _;
+
+use Departures;
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render a board as an Atom feed, one entry per departure.
+///
+/// `self_url` is the feed's own URL, used for the mandatory Atom `<id>`
+/// and self-link.
+pub fn departures_feed(board: &Departures, self_url: &str) -> String {
+    let mut feed = String::new();
+    writeln!(feed, r##"<?xml version="1.0" encoding="utf-8"?>"##).unwrap();
+    writeln!(feed, r##"<feed xmlns="http://www.w3.org/2005/Atom">"##).unwrap();
+    writeln!(feed, "  <id>{}</id>", escape(self_url)).unwrap();
+    writeln!(feed, "  <title>{} departures</title>", escape(&board.stop_name)).unwrap();
+    writeln!(feed, r##"  <link rel="self" href="{}"/>"##, escape(self_url)).unwrap();
+    writeln!(feed, "  <updated>{}</updated>", board.timestamp.to_rfc3339()).unwrap();
+
+    for departure in &board.departures {
+        writeln!(feed, "  <entry>").unwrap();
+        writeln!(feed, "    <id>{}/{}/{}</id>", escape(self_url), escape(&departure.route), departure.time.timestamp()).unwrap();
+        writeln!(
+            feed,
+            "    <title>{} to {} at {}</title>",
+            escape(&departure.route),
+            escape(&departure.destination.terminus),
+            departure.time.format("%H:%M")
+        )
+        .unwrap();
+        writeln!(feed, "    <updated>{}</updated>", departure.time.to_rfc3339()).unwrap();
+        writeln!(feed, "  </entry>").unwrap();
+    }
+
+    writeln!(feed, "</feed>").unwrap();
+    feed
+}
+
+/// Disruptions/service alerts feed isn't supported: this crate has no
+/// source of disruption data, only the live departures board.
+#[derive(Debug)]
+pub struct DisruptionsUnsupported;
+
+impl fmt::Display for DisruptionsUnsupported {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no disruptions data source is available; only departure boards can be rendered as a feed")
+    }
+}
+
+/// Render a disruptions feed. Always fails: see [`DisruptionsUnsupported`].
+pub fn disruptions_feed() -> Result<String, DisruptionsUnsupported> {
+    Err(DisruptionsUnsupported)
+}
@@ -0,0 +1,134 @@
+//! Standalone mock KVV API server, behind `--features mock-server`.
+//!
+//! Serves canned departures/search fixtures instead of talking to the
+//! real API, with configurable latency, injected errors, and
+//! deterministic time progression, so downstream apps can be built and
+//! tested against realistic responses offline and deterministically —
+//! a live API run is neither repeatable nor controllable enough for
+//! testing edge cases like "the board goes stale" or "every third
+//! request fails".
+
+extern crate chrono;
+extern crate rand;
+extern crate serde_json;
+extern crate tiny_http;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use rand::Rng;
+use serde_json::Value;
+use tiny_http::{Header, Response, Server};
+
+struct Config {
+    port: u16,
+    fixtures_dir: PathBuf,
+    latency: Duration,
+    error_rate: f64,
+    advance_seconds: i64,
+}
+
+fn usage() -> &'static str {
+    "usage: kvv-mock-server [--port PORT] [--fixtures DIR] [--latency-ms MS] [--error-rate FRACTION] [--advance-seconds SECONDS]"
+}
+
+fn error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, usage());
+    process::exit(1);
+}
+
+fn take_flag_value(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let pos = args.iter().position(|a| a == flag)?;
+    args.remove(pos);
+    if pos >= args.len() {
+        error(&format!("{} requires a value", flag));
+    }
+    Some(args.remove(pos))
+}
+
+fn parse_args() -> Config {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+
+    let port = take_flag_value(&mut args, "--port").map_or(8089, |v| v.parse().unwrap_or_else(|_| error("--port must be a number")));
+    let fixtures_dir = take_flag_value(&mut args, "--fixtures").map_or_else(|| PathBuf::from("fixtures"), PathBuf::from);
+    let latency_ms =
+        take_flag_value(&mut args, "--latency-ms").map_or(0, |v| v.parse().unwrap_or_else(|_| error("--latency-ms must be a number")));
+    let error_rate =
+        take_flag_value(&mut args, "--error-rate").map_or(0.0, |v| v.parse().unwrap_or_else(|_| error("--error-rate must be a number")));
+    let advance_seconds = take_flag_value(&mut args, "--advance-seconds")
+        .map_or(0, |v| v.parse().unwrap_or_else(|_| error("--advance-seconds must be a number")));
+
+    if !args.is_empty() {
+        error(&format!("unrecognized argument: {}", args[0]));
+    }
+
+    Config { port, fixtures_dir, latency: Duration::from_millis(latency_ms), error_rate, advance_seconds }
+}
+
+fn load_fixture(dir: &Path, name: &str) -> Value {
+    let path = dir.join(name);
+    let text = fs::read_to_string(&path).unwrap_or_else(|e| error(&format!("failed to read fixture {}: {}", path.display(), e)));
+    serde_json::from_str(&text).unwrap_or_else(|e| error(&format!("invalid JSON in {}: {}", path.display(), e)))
+}
+
+/// Advance a departures fixture's `timestamp` field by `requests_served`
+/// times `advance_seconds`, so repeated polls of the mock server see the
+/// board's reported time progress deterministically, without depending
+/// on wall-clock time or mutating the fixture file itself.
+fn advance_timestamp(departures: &mut Value, requests_served: i64, advance_seconds: i64) {
+    if advance_seconds == 0 {
+        return;
+    }
+    let advanced = departures.get("timestamp").and_then(Value::as_str).and_then(|ts| {
+        chrono::NaiveDateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S")
+            .ok()
+            .map(|parsed| (parsed + chrono::Duration::seconds(advance_seconds * requests_served)).format("%Y-%m-%d %H:%M:%S").to_string())
+    });
+    if let Some(advanced) = advanced {
+        departures["timestamp"] = Value::String(advanced);
+    }
+}
+
+fn json_response(body: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("static header is valid");
+    Response::from_string(body).with_header(header)
+}
+
+fn main() {
+    let config = parse_args();
+    let departures_fixture = load_fixture(&config.fixtures_dir, "departures.json");
+    let search_fixture = load_fixture(&config.fixtures_dir, "search.json");
+
+    let server = Server::http(("0.0.0.0", config.port)).unwrap_or_else(|e| error(&format!("failed to bind port {}: {}", config.port, e)));
+    println!("kvv-mock-server listening on 0.0.0.0:{} (fixtures: {})", config.port, config.fixtures_dir.display());
+
+    let requests_served = AtomicU64::new(0);
+
+    for request in server.incoming_requests() {
+        let served = requests_served.fetch_add(1, Ordering::Relaxed) as i64;
+
+        if !config.latency.is_zero() {
+            thread::sleep(config.latency);
+        }
+
+        if config.error_rate > 0.0 && rand::thread_rng().gen::<f64>() < config.error_rate {
+            let _ = request.respond(Response::from_string("internal error injected by kvv-mock-server").with_status_code(500));
+            continue;
+        }
+
+        let url = request.url().to_owned();
+        if url.starts_with("/departures/") {
+            let mut fixture = departures_fixture.clone();
+            advance_timestamp(&mut fixture, served, config.advance_seconds);
+            let _ = request.respond(json_response(fixture.to_string()));
+        } else if url.starts_with("/stops/") {
+            let _ = request.respond(json_response(search_fixture.to_string()));
+        } else {
+            let _ = request.respond(Response::from_string("not found").with_status_code(404));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+//! Rendering a departure board to a PNG, for chat bots, e-mail digests,
+//! and picture-frame displays that can only show an image rather than
+//! render text themselves.
+//!
+//! Gated behind the `png-render` feature since it pulls in `image` and
+//! `imageproc`/`ab_glyph` for font rasterization, which most consumers of
+//! this crate (a CLI, a library embedded elsewhere) don't need.
+
+use ab_glyph::{FontRef, InvalidFont, PxScale};
+use image::{Rgb, RgbImage};
+use imageproc::drawing::{draw_filled_rect_mut, draw_text_mut, text_size};
+use imageproc::rect::Rect;
+
+use Departure;
+use Departures;
+
+/// Visual parameters for [`render`]. Callers supply their own font bytes
+/// (e.g. loaded from a `.ttf`/`.otf` file) since this crate doesn't ship
+/// or embed one.
+pub struct BoardStyle<'a> {
+    pub width: u32,
+    /// height of each departure row, in pixels; the image height is
+    /// derived from this and the number of rows
+    pub row_height: u32,
+    pub font_bytes: &'a [u8],
+    pub font_size: f32,
+    pub text_color: Rgb<u8>,
+    pub background_color: Rgb<u8>,
+}
+
+impl<'a> Default for BoardStyle<'a> {
+    fn default() -> BoardStyle<'a> {
+        BoardStyle {
+            width: 640,
+            row_height: 40,
+            font_bytes: &[],
+            font_size: 24.0,
+            text_color: Rgb([255, 255, 255]),
+            background_color: Rgb([0, 0, 0]),
+        }
+    }
+}
+
+fn row_text(departure: &Departure) -> String {
+    let rt = if departure.realtime { "*" } else { " " };
+    format!("{:<3} {:<20} {}{}", departure.route, departure.destination, departure.time.format("%H:%M"), rt)
+}
+
+/// Render a departure board to a PNG, one row per departure plus a
+/// header row with the stop name.
+pub fn render(board: &Departures, style: &BoardStyle) -> Result<RgbImage, InvalidFont> {
+    let font = FontRef::try_from_slice(style.font_bytes)?;
+    let scale = PxScale::from(style.font_size);
+
+    let rows = board.departures.len() as u32 + 1;
+    let height = rows * style.row_height;
+    let mut image = RgbImage::new(style.width, height);
+    draw_filled_rect_mut(&mut image, Rect::at(0, 0).of_size(style.width, height), style.background_color);
+
+    let pad_x = 8;
+    let line_y = |row: u32| (row * style.row_height) as i32 + (style.row_height as i32 - style.font_size as i32) / 2;
+
+    draw_text_mut(&mut image, style.text_color, pad_x, line_y(0), scale, &font, &board.stop_name);
+    for (i, departure) in board.departures.iter().enumerate() {
+        let row = i as u32 + 1;
+        let text = row_text(departure);
+        let (text_width, _) = text_size(scale, &font, &text);
+        let x = if text_width > style.width { 0 } else { pad_x };
+        draw_text_mut(&mut image, style.text_color, x, line_y(row), scale, &font, &text);
+    }
+
+    Ok(image)
+}
@@ -0,0 +1,32 @@
+//! A simple on-disk cache of the last successfully fetched board per stop,
+//! so callers (notably the CLI) can fall back to it when the network is
+//! unreachable instead of failing outright.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde_json;
+
+use metrics;
+use paths;
+use Departures;
+
+fn cache_path(stop_id: &str) -> PathBuf {
+    paths::cache_dir().join(format!("{}.json", stop_id.replace(':', "_")))
+}
+
+/// Persist the given board as the last-known-good board for this stop.
+pub fn store(stop_id: &str, departures: &Departures) -> io::Result<()> {
+    fs::create_dir_all(paths::cache_dir())?;
+    let json = serde_json::to_string(departures).map_err(io::Error::other)?;
+    fs::write(cache_path(stop_id), json)
+}
+
+/// Load the last-known-good board for this stop, if one was ever cached.
+pub fn load(stop_id: &str) -> io::Result<Departures> {
+    let result = fs::read_to_string(cache_path(stop_id))
+        .and_then(|contents| serde_json::from_str(&contents).map_err(io::Error::other));
+    metrics::record_cache_lookup(result.is_ok());
+    result
+}
@@ -0,0 +1,109 @@
+//! Detection of likely cancelled departures by comparing consecutive boards.
+//!
+//! The live API has no explicit "cancelled" status: a cancelled tram simply
+//! disappears from the board. If it vanishes while it was still well in the
+//! future, that is a much stronger signal than a two-minute delay, so this
+//! is tracked separately from ordinary board changes.
+
+use chrono::Duration;
+
+use {Departure, Departures};
+
+/// How far in the future a departure must still have been to count as
+/// "likely cancelled" rather than just having already left.
+const DEFAULT_LEAD_TIME: i64 = 3; // minutes
+
+/// A departure that was present on a previous board but is missing from the
+/// current one, despite not having been due to depart yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LikelyCancelled {
+    /// the departure as last seen
+    pub departure: Departure,
+    /// how many minutes before its predicted time it vanished
+    pub lead_minutes: i64,
+}
+
+fn matches(a: &Departure, b: &Departure) -> bool {
+    a.route == b.route && a.destination == b.destination && a.direction == b.direction
+}
+
+/// Compare two boards for the same stop and return departures that were
+/// present in `previous` but are missing from `current`, while their
+/// predicted time was still more than `lead_time` minutes away.
+///
+/// `lead_time` guards against flagging departures that simply already left.
+pub fn detect_cancellations_with_lead_time(
+    previous: &Departures,
+    current: &Departures,
+    lead_time: Duration,
+) -> Vec<LikelyCancelled> {
+    previous
+        .departures
+        .iter()
+        .filter(|prev| !current.departures.iter().any(|cur| matches(prev, cur)))
+        .filter_map(|prev| {
+            let lead = prev.time.signed_duration_since(current.timestamp);
+            if lead > lead_time {
+                Some(LikelyCancelled {
+                    departure: prev.clone(),
+                    lead_minutes: lead.num_minutes(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Like [`detect_cancellations_with_lead_time`], using the crate's default
+/// lead time of a few minutes.
+pub fn detect_cancellations(previous: &Departures, current: &Departures) -> Vec<LikelyCancelled> {
+    detect_cancellations_with_lead_time(previous, current, Duration::minutes(DEFAULT_LEAD_TIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+    use chrono_tz::Tz;
+    use {Departures, Destination};
+
+    fn at(minute: u32) -> chrono::DateTime<Tz> {
+        Berlin.with_ymd_and_hms(2026, 8, 9, 12, minute, 0).unwrap()
+    }
+
+    fn departure(route: &str, minute: u32) -> Departure {
+        Departure::new(route, Destination::new(route, Vec::new(), route), "1", at(minute), false, true, 0)
+    }
+
+    fn board(timestamp_minute: u32, departures: Vec<Departure>) -> Departures {
+        Departures::new(at(timestamp_minute), "Test", departures)
+    }
+
+    #[test]
+    fn flags_a_departure_that_vanished_well_before_its_time() {
+        let previous = board(0, vec![departure("S2", 10)]);
+        let current = board(0, vec![]);
+        let cancelled = detect_cancellations_with_lead_time(&previous, &current, Duration::minutes(3));
+        assert_eq!(cancelled.len(), 1);
+        assert_eq!(cancelled[0].departure.route, "S2");
+        assert_eq!(cancelled[0].lead_minutes, 10);
+    }
+
+    #[test]
+    fn does_not_flag_a_departure_that_simply_already_left() {
+        let previous = board(0, vec![departure("S2", 1)]);
+        let current = board(2, vec![]);
+        let cancelled = detect_cancellations_with_lead_time(&previous, &current, Duration::minutes(3));
+        assert!(cancelled.is_empty());
+    }
+
+    #[test]
+    fn does_not_flag_a_departure_still_present_on_both_boards() {
+        let previous = board(0, vec![departure("S2", 10)]);
+        let current = board(0, vec![departure("S2", 10)]);
+        let cancelled = detect_cancellations_with_lead_time(&previous, &current, Duration::minutes(3));
+        assert!(cancelled.is_empty());
+    }
+}
@@ -0,0 +1,669 @@
+//! A thread-safe, cheaply clonable client handle, as an alternative to
+//! the crate's free functions (which share one implicit global client via
+//! [`preset::active`](::preset::active) and friends). Each `KvvClient`
+//! carries its own HTTP connection pool, [`EfaPreset`], and request
+//! settings behind an `Arc`, so one configured instance can be handed to
+//! as many worker threads as you like without wrapping it in a `Mutex`.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration as StdDuration, Instant};
+
+use chrono::{DateTime, Duration, Local};
+use chrono_tz::Tz;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::{Client as HttpClient, StatusCode};
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use endpoint::Endpoint;
+use metrics;
+use preset::EfaPreset;
+use raw;
+use schema;
+use vcr;
+use vcr::Cassette;
+use {Departures, SearchAnswer, Stop};
+
+fn build_http_client(timeout: StdDuration, gzip: bool) -> HttpClient {
+    HttpClient::builder().timeout(timeout).gzip(gzip).build().expect("building an HTTP client with a timeout should never fail")
+}
+
+/// How much of a failing response body to keep in [`ClientError::body_snippet`].
+const BODY_SNIPPET_MAX_CHARS: usize = 200;
+
+/// Above this latency, a successful [`KvvClient::health_check`] reports
+/// [`HealthStatus::Degraded`] instead of [`HealthStatus::Ok`].
+const DEGRADED_LATENCY: StdDuration = StdDuration::from_secs(3);
+
+/// Coarse classification of upstream reachability, returned by
+/// [`KvvClient::health_check`] for a daemon's readiness probe or the
+/// CLI's `doctor` subcommand to act on without inspecting a
+/// [`ClientError`] itself.
+///
+/// `#[non_exhaustive]` so a finer-grained variant can be added later
+/// (e.g. distinguishing a slow upstream from a rate limit) without
+/// breaking every downstream match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HealthStatus {
+    /// The probe request succeeded within [`DEGRADED_LATENCY`].
+    Ok,
+    /// The probe request succeeded, but took longer than
+    /// [`DEGRADED_LATENCY`] — upstream is reachable but slow.
+    Degraded,
+    /// The probe request never got a response: DNS failure, connection
+    /// refused, or the request timed out.
+    Unreachable,
+    /// The upstream responded but rejected the request, consistent with
+    /// an invalid or revoked API key.
+    AuthProblem,
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &s[..end]),
+        None => s.to_owned(),
+    }
+}
+
+/// Redact the `key` query parameter from `url`, so it's safe to log or
+/// put in an error message without leaking the API key.
+fn redact_key(url: &Url) -> String {
+    let mut redacted = url.clone();
+    let pairs: Vec<(String, String)> =
+        url.query_pairs().map(|(k, v)| if k == "key" { (k.into_owned(), "REDACTED".to_owned()) } else { (k.into_owned(), v.into_owned()) }).collect();
+    redacted.query_pairs_mut().clear().extend_pairs(&pairs);
+    redacted.into_string()
+}
+
+/// Error returned by [`KvvClient`]'s methods. Wraps either the underlying
+/// `reqwest::Error` or a JSON decoding error, erased to a message: when a
+/// request is [coalesced](KvvClient#request-coalescing) with identical
+/// concurrent requests, every waiter shares the one leader's outcome, and
+/// `reqwest::Error`/`serde_json::Error` aren't `Clone`, so there's nothing
+/// richer to hand back to the followers than the message.
+///
+/// Carries enough context to debug a failure without adding print
+/// statements: [`endpoint`](ClientError::endpoint) (API key redacted) and,
+/// when the body was already read before the failure, a
+/// [`body_snippet`](ClientError::body_snippet) of it.
+#[derive(Debug, Clone)]
+pub struct ClientError {
+    message: String,
+    status: Option<StatusCode>,
+    endpoint: Option<String>,
+    body_snippet: Option<String>,
+}
+
+impl ClientError {
+    /// The HTTP status code that caused this error, if it was one.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    /// The request URL that failed, with the `key` query parameter
+    /// redacted to `REDACTED` — safe to log.
+    pub fn endpoint(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// The first [`BODY_SNIPPET_MAX_CHARS`] characters of the raw
+    /// response body, if one was read before this error occurred (e.g. a
+    /// body that failed to parse as JSON, or a non-2xx response).
+    pub fn body_snippet(&self) -> Option<&str> {
+        self.body_snippet.as_deref()
+    }
+
+    fn with_endpoint(mut self, url: &Url) -> ClientError {
+        self.endpoint = Some(redact_key(url));
+        self
+    }
+
+    fn with_body_snippet(mut self, body: &str) -> ClientError {
+        self.body_snippet = Some(truncate(body, BODY_SNIPPET_MAX_CHARS));
+        self
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(endpoint) = &self.endpoint {
+            write!(f, " (endpoint: {})", endpoint)?;
+        }
+        if let Some(body_snippet) = &self.body_snippet {
+            write!(f, " (body: {:?})", body_snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> ClientError {
+        ClientError { status: e.status(), message: e.to_string(), endpoint: None, body_snippet: None }
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(e: serde_json::Error) -> ClientError {
+        ClientError { status: None, message: e.to_string(), endpoint: None, body_snippet: None }
+    }
+}
+
+/// The result of one upstream request, shared between a coalesced
+/// request's leader and any followers that arrived while it was in
+/// flight.
+struct InFlight {
+    body: Mutex<Option<Result<String, ClientError>>>,
+    done: Condvar,
+}
+
+/// The conditional-request validators and body last seen for one URL, so
+/// the next request for it can ask the upstream "has this changed?"
+/// instead of re-downloading a board that's still current.
+#[derive(Clone)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: String,
+}
+
+struct ClientState {
+    http: Mutex<HttpClient>,
+    timeout: Mutex<StdDuration>,
+    gzip: AtomicBool,
+    preset: EfaPreset,
+    retries: AtomicU32,
+    inflight: Mutex<HashMap<String, Arc<InFlight>>>,
+    validators: Mutex<HashMap<String, Validators>>,
+    cassette: Option<Cassette>,
+    /// Clock skew observed between this machine and `preset`'s server,
+    /// kept per-instance (unlike [`clockskew`](::clockskew), the
+    /// free-function API's process-global estimate) so two `KvvClient`s
+    /// pointed at different deployments never clobber each other's skew.
+    skew: Mutex<Duration>,
+}
+
+/// A configured handle to the KVV live API, independent of the crate's
+/// global preset/timeout/retries settings. `Clone + Send + Sync`: clones
+/// share the same connection pool and settings as the original.
+///
+/// ## Request coalescing
+///
+/// If several threads sharing a `KvvClient` call the same endpoint with
+/// the same arguments while a request for it is already in flight (e.g.
+/// a server mode handling a burst of requests for one popular stop),
+/// only the first ("leader") actually hits the network; the rest block
+/// and share its result. This trades a little contention on the
+/// in-flight table for not hammering the upstream API with duplicate
+/// work.
+///
+/// ## Deadlines
+///
+/// Calls block for as long as the underlying HTTP request takes (plus
+/// retries). To give up waiting after a fixed point in time instead, see
+/// [`fetch_with_deadline`](KvvClient::fetch_with_deadline). To override
+/// the timeout for just one call (e.g. a short one for an interactive
+/// search versus a long one for a background poll), use
+/// [`request`](KvvClient::request)`().timeout(...)`.
+///
+/// ## Recording and replay
+///
+/// A client built with [`with_cassette`](KvvClient::with_cassette) reads
+/// from or writes to a [`vcr::Cassette`] instead of always hitting the
+/// network — see that module for recording fixtures for hermetic tests.
+///
+/// ## Conditional requests
+///
+/// If the upstream returned an `ETag` or `Last-Modified` header for a URL,
+/// the next request for that same URL sends it back as `If-None-Match` /
+/// `If-Modified-Since`. A `304 Not Modified` response then costs only the
+/// headers, not the board's full JSON body, and the previously-seen body
+/// is returned as if it had been fetched again. Whether each request was
+/// answered from a 304 is recorded via
+/// [`metrics::record_not_modified`](::metrics::record_not_modified), for
+/// a caching layer (or just curiosity) to check via [`metrics::snapshot`](::metrics::snapshot).
+#[derive(Clone)]
+pub struct KvvClient {
+    state: Arc<ClientState>,
+}
+
+impl KvvClient {
+    /// A client using `preset` and the default timeout (30s) and retry
+    /// count (0).
+    pub fn new(preset: EfaPreset) -> KvvClient {
+        KvvClient {
+            state: Arc::new(ClientState {
+                http: Mutex::new(build_http_client(StdDuration::from_secs(30), true)),
+                timeout: Mutex::new(StdDuration::from_secs(30)),
+                gzip: AtomicBool::new(true),
+                preset,
+                retries: AtomicU32::new(0),
+                inflight: Mutex::new(HashMap::new()),
+                validators: Mutex::new(HashMap::new()),
+                cassette: None,
+                skew: Mutex::new(Duration::zero()),
+            }),
+        }
+    }
+
+    /// A client preconfigured for the KVV (Karlsruhe) network.
+    pub fn kvv() -> KvvClient {
+        KvvClient::new(EfaPreset::kvv())
+    }
+
+    /// A client using `preset` that replays responses from (or records
+    /// them to) `cassette` instead of always talking to the network —
+    /// see [`vcr`] — with the default timeout (30s) and retry count (0).
+    pub fn with_cassette(preset: EfaPreset, cassette: Cassette) -> KvvClient {
+        KvvClient {
+            state: Arc::new(ClientState {
+                http: Mutex::new(build_http_client(StdDuration::from_secs(30), true)),
+                timeout: Mutex::new(StdDuration::from_secs(30)),
+                gzip: AtomicBool::new(true),
+                preset,
+                retries: AtomicU32::new(0),
+                inflight: Mutex::new(HashMap::new()),
+                validators: Mutex::new(HashMap::new()),
+                cassette: Some(cassette),
+                skew: Mutex::new(Duration::zero()),
+            }),
+        }
+    }
+
+    pub fn set_timeout(&self, timeout: StdDuration) {
+        *self.state.timeout.lock().unwrap() = timeout;
+        self.rebuild_http();
+    }
+
+    pub fn set_retries(&self, retries: u32) {
+        self.state.retries.store(retries, Ordering::Relaxed);
+    }
+
+    /// Enable or disable gzip/deflate response compression (on by
+    /// default): sets `Accept-Encoding: gzip` on outgoing requests and
+    /// transparently decompresses a compressed response. Turn it off to
+    /// rule out a decompression bug while debugging, or to trade upstream
+    /// CPU for one less processing step on a very low-power client —
+    /// most callers, especially those polling repeatedly over a metered
+    /// connection, want it left on.
+    pub fn set_gzip(&self, enable: bool) {
+        self.state.gzip.store(enable, Ordering::Relaxed);
+        self.rebuild_http();
+    }
+
+    fn rebuild_http(&self) {
+        let timeout = *self.state.timeout.lock().unwrap();
+        let gzip = self.state.gzip.load(Ordering::Relaxed);
+        *self.state.http.lock().unwrap() = build_http_client(timeout, gzip);
+    }
+
+    /// Run a `KvvClient` call against a deadline instead of waiting on it
+    /// indefinitely, e.g. `KvvClient::fetch_with_deadline(move || client.departures_by_stop(&stop_id), deadline)`
+    /// for an interactive search that should give up if the user has
+    /// already navigated away.
+    ///
+    /// This crate's HTTP layer is synchronous (reqwest's blocking
+    /// client), so there's no future to drop and no way to truly abort
+    /// the in-flight request: `f` keeps running to completion on its own
+    /// thread regardless, its result just discarded if it arrives after
+    /// `deadline`. That's enough to stop a caller from blocking past the
+    /// deadline, which is usually what matters for UI responsiveness.
+    pub fn fetch_with_deadline<T, F>(f: F, deadline: Instant) -> Result<T, ClientError>
+    where
+        F: FnOnce() -> Result<T, ClientError> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(f());
+        });
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(result) => result,
+            Err(_) => Err(ClientError {
+                status: None,
+                message: "deadline exceeded before the request completed".to_owned(),
+                endpoint: None,
+                body_snippet: None,
+            }),
+        }
+    }
+
+    /// A builder for one call that may override this client's settings
+    /// just for that call, e.g. [`timeout`](RequestBuilder::timeout).
+    pub fn request(&self) -> RequestBuilder<'_> {
+        RequestBuilder { client: self, timeout: None }
+    }
+
+    /// Send one already-built request, retrying on the client's
+    /// configured retry count. `http` is whichever client — the shared
+    /// pooled one, or a one-off built for a per-call timeout override —
+    /// should actually perform the send.
+    ///
+    /// If this client has a [`vcr::Cassette`], it's consulted first: in
+    /// [`vcr::Mode::Replay`] a recorded body short-circuits the network
+    /// entirely (or the call fails if nothing was recorded for this
+    /// URL), and in [`vcr::Mode::Record`] a successful live response is
+    /// captured into it afterwards.
+    fn send_retrying(&self, http: &HttpClient, url: &Url) -> Result<String, ClientError> {
+        if let Some(cassette) = &self.state.cassette {
+            if let Some(body) = cassette.replay(url.as_str()) {
+                return Ok(body);
+            }
+            if cassette.mode() == vcr::Mode::Replay {
+                return Err(ClientError {
+                    status: None,
+                    message: "no cassette recording for this endpoint".to_owned(),
+                    endpoint: Some(redact_key(url)),
+                    body_snippet: None,
+                });
+            }
+        }
+
+        let mut attempts_left = self.state.retries.load(Ordering::Relaxed) + 1;
+        let result = loop {
+            let attempt = self.send_once(http, url);
+            attempts_left -= 1;
+            match attempt {
+                Ok(text) => break Ok(text),
+                Err(e) => {
+                    if attempts_left == 0 {
+                        break Err(e);
+                    }
+                }
+            }
+        };
+
+        if let (Some(cassette), Ok(body)) = (&self.state.cassette, &result) {
+            let _ = cassette.record(url.as_str(), body);
+        }
+
+        result
+    }
+
+    /// Send one GET request, attaching `If-None-Match`/`If-Modified-Since`
+    /// from a previous response for this URL if we have them. A `304`
+    /// short-circuits to the body we already have instead of re-reading
+    /// it off the wire; anything else updates the stored validators for
+    /// next time.
+    fn send_once(&self, http: &HttpClient, url: &Url) -> Result<String, ClientError> {
+        let key = url.as_str().to_owned();
+        let cached = self.state.validators.lock().unwrap().get(&key).cloned();
+
+        let mut builder = http.get(url.clone());
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                builder = builder.header(IF_NONE_MATCH, etag.as_str());
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                builder = builder.header(IF_MODIFIED_SINCE, last_modified.as_str());
+            }
+        }
+
+        let mut response = builder.send().map_err(|e| ClientError::from(e).with_endpoint(url))?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(cached) = cached {
+                metrics::record_not_modified(true);
+                return Ok(cached.body);
+            }
+        }
+        metrics::record_not_modified(false);
+
+        let status = response.status();
+        let etag = response.headers().get(ETAG).and_then(|v| v.to_str().ok()).map(str::to_owned);
+        let last_modified = response.headers().get(LAST_MODIFIED).and_then(|v| v.to_str().ok()).map(str::to_owned);
+        let text = response.text().map_err(|e| ClientError::from(e).with_endpoint(url))?;
+
+        if !status.is_success() {
+            return Err(ClientError { message: format!("upstream returned {}", status), status: Some(status), endpoint: None, body_snippet: None }
+                .with_endpoint(url)
+                .with_body_snippet(&text));
+        }
+
+        let mut validators = self.state.validators.lock().unwrap();
+        if etag.is_some() || last_modified.is_some() {
+            validators.insert(key, Validators { etag, last_modified, body: text.clone() });
+        } else {
+            validators.remove(&key);
+        }
+
+        Ok(text)
+    }
+
+    /// Fetch `url`'s response body as text, coalescing with any other
+    /// request for the same URL that's already in flight on this client.
+    fn fetch_coalesced(&self, url: &Url) -> Result<String, ClientError> {
+        let key = url.as_str().to_owned();
+
+        let (inflight, is_leader) = {
+            let mut table = self.state.inflight.lock().unwrap();
+            if let Some(existing) = table.get(&key) {
+                (existing.clone(), false)
+            } else {
+                let inflight = Arc::new(InFlight { body: Mutex::new(None), done: Condvar::new() });
+                table.insert(key.clone(), inflight.clone());
+                (inflight, true)
+            }
+        };
+
+        if !is_leader {
+            let mut body = inflight.body.lock().unwrap();
+            while body.is_none() {
+                body = inflight.done.wait(body).unwrap();
+            }
+            return body.clone().unwrap();
+        }
+
+        let result = self.send_retrying(&self.state.http.lock().unwrap(), url);
+
+        self.state.inflight.lock().unwrap().remove(&key);
+        *inflight.body.lock().unwrap() = Some(result.clone());
+        inflight.done.notify_all();
+        result
+    }
+
+    fn query_with<T: DeserializeOwned>(&self, endpoint: Endpoint, timeout: Option<StdDuration>) -> Result<T, ClientError> {
+        let url = endpoint
+            .to_url(&self.state.preset.base_url, &self.state.preset.key)
+            .map_err(|e| ClientError { status: None, message: e.to_string(), endpoint: None, body_snippet: None })?;
+
+        let body = match timeout {
+            // A per-call timeout override can't reuse the shared pooled
+            // client (reqwest 0.9 has no per-request timeout), so it
+            // gets a one-off client and skips coalescing — sharing a
+            // result fetched under someone else's timeout would make
+            // the override meaningless.
+            Some(t) => self.send_retrying(&build_http_client(t, self.state.gzip.load(Ordering::Relaxed)), &url)?,
+            None => self.fetch_coalesced(&url)?,
+        };
+        serde_json::from_str(&body).map_err(|e| ClientError::from(e).with_endpoint(&url).with_body_snippet(&body))
+    }
+
+    fn query<T: DeserializeOwned>(&self, endpoint: Endpoint) -> Result<T, ClientError> {
+        self.query_with(endpoint, None)
+    }
+
+    fn search(&self, endpoint: Endpoint) -> Result<Vec<Stop>, ClientError> {
+        self.query::<SearchAnswer>(endpoint).map(|s| s.stops)
+    }
+
+    /// Record the skew implied by a board's response timestamp, for later
+    /// retrieval via [`skew`](KvvClient::skew)/[`correct`](KvvClient::correct).
+    fn observe_skew(&self, server_timestamp: DateTime<Tz>) {
+        let local_now = Local::now().with_timezone(&server_timestamp.timezone());
+        *self.state.skew.lock().unwrap() = server_timestamp.signed_duration_since(local_now);
+    }
+
+    /// The skew between this machine's clock and this client's preset's
+    /// server, as observed from the most recently fetched board. Zero
+    /// until the first successful board fetch.
+    ///
+    /// Unlike [`clockskew::current_skew`](::clockskew::current_skew), this
+    /// is tracked per `KvvClient` instance, not as one process-global
+    /// estimate shared by every client.
+    pub fn skew(&self) -> Duration {
+        *self.state.skew.lock().unwrap()
+    }
+
+    /// Correct a local-clock-relative time by this client's current skew
+    /// estimate — see [`skew`](KvvClient::skew).
+    pub fn correct(&self, local_time: DateTime<Tz>) -> DateTime<Tz> {
+        local_time + self.skew()
+    }
+
+    pub fn departures_by_stop(&self, stop_id: &str) -> Result<Departures, ClientError> {
+        let deps: Departures = self.query(Endpoint::DeparturesByStop { stop_id })?;
+        self.observe_skew(deps.timestamp);
+        Ok(deps)
+    }
+
+    pub fn departures_by_route(&self, stop_id: &str, route: &str) -> Result<Departures, ClientError> {
+        let deps: Departures = self.query(Endpoint::DeparturesByRoute { stop_id, route })?;
+        self.observe_skew(deps.timestamp);
+        Ok(deps)
+    }
+
+    /// Like [`departures_by_stop`](KvvClient::departures_by_stop), but
+    /// parsed leniently via [`raw::RawDepartures`](::raw::RawDepartures):
+    /// a departure with a malformed field is dropped, with a
+    /// [`raw::ParseIssue`](::raw::ParseIssue) explaining why, instead of
+    /// failing the whole board.
+    pub fn departures_by_stop_lenient(&self, stop_id: &str) -> Result<(Departures, Vec<raw::ParseIssue>), ClientError> {
+        let raw: raw::RawDepartures = self.query(Endpoint::DeparturesByStop { stop_id })?;
+        let (deps, issues) = raw.into_domain();
+        self.observe_skew(deps.timestamp);
+        Ok((deps, issues))
+    }
+
+    /// Like [`departures_by_route`](KvvClient::departures_by_route), but
+    /// parsed leniently via [`raw::RawDepartures`](::raw::RawDepartures):
+    /// a departure with a malformed field is dropped, with a
+    /// [`raw::ParseIssue`](::raw::ParseIssue) explaining why, instead of
+    /// failing the whole board.
+    pub fn departures_by_route_lenient(&self, stop_id: &str, route: &str) -> Result<(Departures, Vec<raw::ParseIssue>), ClientError> {
+        let raw: raw::RawDepartures = self.query(Endpoint::DeparturesByRoute { stop_id, route })?;
+        let (deps, issues) = raw.into_domain();
+        self.observe_skew(deps.timestamp);
+        Ok((deps, issues))
+    }
+
+    /// Like [`departures_by_stop`](KvvClient::departures_by_stop), but also
+    /// compares the raw response against this crate's known [`schema`] and
+    /// returns any drift alongside the parsed board — for CI jobs and
+    /// daemons that want to notice an upstream format change before it
+    /// breaks a board outright.
+    pub fn departures_by_stop_checked(&self, stop_id: &str) -> Result<(Departures, Vec<schema::Drift>), ClientError> {
+        let value = self.get_raw(Endpoint::DeparturesByStop { stop_id })?;
+        let drifts = schema::check_departures(&value);
+        let deps = Departures::from_value(value)?;
+        self.observe_skew(deps.timestamp);
+        Ok((deps, drifts))
+    }
+
+    /// Like [`departures_by_route`](KvvClient::departures_by_route), but
+    /// also compares the raw response against this crate's known
+    /// [`schema`] and returns any drift alongside the parsed board.
+    pub fn departures_by_route_checked(&self, stop_id: &str, route: &str) -> Result<(Departures, Vec<schema::Drift>), ClientError> {
+        let value = self.get_raw(Endpoint::DeparturesByRoute { stop_id, route })?;
+        let drifts = schema::check_departures(&value);
+        let deps = Departures::from_value(value)?;
+        self.observe_skew(deps.timestamp);
+        Ok((deps, drifts))
+    }
+
+    pub fn search_by_name(&self, name: &str) -> Result<Vec<Stop>, ClientError> {
+        self.search(Endpoint::SearchByName { name })
+    }
+
+    pub fn search_by_latlon(&self, lat: f64, lon: f64) -> Result<Vec<Stop>, ClientError> {
+        self.search(Endpoint::SearchByLatLon { lat, lon })
+    }
+
+    pub fn search_by_stop_id(&self, stop_id: &str) -> Result<Option<Stop>, ClientError> {
+        match self.query(Endpoint::SearchByStopId { stop_id }) {
+            Ok(s) => Ok(Some(s)),
+            Err(e) => match e.status {
+                Some(StatusCode::BAD_REQUEST) => Ok(None),
+                _ => Err(e),
+            },
+        }
+    }
+
+    /// Fetch `endpoint`'s response as a raw [`serde_json::Value`] instead
+    /// of this crate's typed model, for inspecting a field the model
+    /// doesn't cover yet (and reporting it upstream) without reaching for
+    /// a separate `reqwest` call that has to rediscover the base URL and
+    /// API key this client already knows. Parse it into a typed model
+    /// later with e.g. [`Departures::from_value`](::Departures::from_value).
+    pub fn get_raw(&self, endpoint: Endpoint) -> Result<serde_json::Value, ClientError> {
+        self.query(endpoint)
+    }
+
+    /// Probe the upstream with a cheap, known-good request (an empty-name
+    /// stop search, which every EFA deployment answers without depending
+    /// on any particular stop existing) and classify the outcome as a
+    /// [`HealthStatus`]. Useful as a daemon's readiness probe or behind
+    /// the CLI's `doctor` subcommand — cheaper than inspecting a
+    /// [`ClientError`] from a real call by hand every time.
+    pub fn health_check(&self) -> HealthStatus {
+        let start = Instant::now();
+        let result = self.search_by_name("");
+        let elapsed = start.elapsed();
+
+        match result {
+            Ok(_) => {
+                if elapsed > DEGRADED_LATENCY {
+                    HealthStatus::Degraded
+                } else {
+                    HealthStatus::Ok
+                }
+            }
+            Err(e) => match e.status() {
+                Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN) => HealthStatus::AuthProblem,
+                Some(_) => HealthStatus::Degraded,
+                None => HealthStatus::Unreachable,
+            },
+        }
+    }
+}
+
+/// One call's settings, built from [`KvvClient::request`]. Currently the
+/// only override is [`timeout`](RequestBuilder::timeout); everything
+/// else about the call comes from the `KvvClient` it was built from.
+pub struct RequestBuilder<'a> {
+    client: &'a KvvClient,
+    timeout: Option<StdDuration>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// Override the client's default timeout for just this call, e.g. a
+    /// short timeout for an interactive search versus a long one for a
+    /// background poll.
+    pub fn timeout(mut self, timeout: StdDuration) -> RequestBuilder<'a> {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn departures_by_stop(self, stop_id: &str) -> Result<Departures, ClientError> {
+        self.client.query_with(Endpoint::DeparturesByStop { stop_id }, self.timeout)
+    }
+
+    pub fn departures_by_route(self, stop_id: &str, route: &str) -> Result<Departures, ClientError> {
+        self.client.query_with(Endpoint::DeparturesByRoute { stop_id, route }, self.timeout)
+    }
+
+    pub fn search_by_name(self, name: &str) -> Result<Vec<Stop>, ClientError> {
+        self.client.query_with::<SearchAnswer>(Endpoint::SearchByName { name }, self.timeout).map(|s| s.stops)
+    }
+
+    pub fn search_by_latlon(self, lat: f64, lon: f64) -> Result<Vec<Stop>, ClientError> {
+        self.client.query_with::<SearchAnswer>(Endpoint::SearchByLatLon { lat, lon }, self.timeout).map(|s| s.stops)
+    }
+}
@@ -0,0 +1,47 @@
+//! Tracking clock skew between this machine and the API server, for
+//! devices with drifting RTCs (e.g. a Raspberry Pi without NTP) that would
+//! otherwise show countdowns that are systematically early or late.
+//!
+//! Every successful [`Departures`](::Departures) response's `timestamp`
+//! is compared against local time in [`observe`]; the most recent
+//! difference is kept as the current estimate and applied by [`correct`].
+//!
+//! Skew is tracked per EFA deployment (keyed by [`preset::active`]'s
+//! `base_url`), not as one process-wide estimate: polling two different
+//! deployments (see [`preset::set_active`]) from the same process would
+//! otherwise let one deployment's skew clobber the other's.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, Duration, Local};
+use chrono_tz::Tz;
+
+use preset;
+
+fn skew_table() -> &'static Mutex<HashMap<String, Duration>> {
+    static SKEW: OnceLock<Mutex<HashMap<String, Duration>>> = OnceLock::new();
+    SKEW.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record the skew implied by a server timestamp, for the currently
+/// [`active`](preset::active) EFA deployment: positive if the server is
+/// ahead of this machine's clock.
+pub fn observe(server_timestamp: DateTime<Tz>) {
+    let local_now = Local::now().with_timezone(&server_timestamp.timezone());
+    let skew = server_timestamp.signed_duration_since(local_now);
+    skew_table().lock().unwrap().insert(preset::active().base_url, skew);
+}
+
+/// The most recently observed skew for the currently active EFA
+/// deployment. Zero until the first [`observe`] call for that deployment.
+pub fn current_skew() -> Duration {
+    skew_table().lock().unwrap().get(&preset::active().base_url).copied().unwrap_or_else(Duration::zero)
+}
+
+/// Correct a local-clock-relative time (e.g. "now" on this machine) by the
+/// current skew estimate, so relative countdowns line up with the
+/// server's notion of time rather than this machine's.
+pub fn correct(local_time: DateTime<Tz>) -> DateTime<Tz> {
+    local_time + current_skew()
+}
@@ -0,0 +1,97 @@
+//! A shared, serde-based (TOML) configuration format for the CLI, daemon,
+//! and server modes, with named profiles (e.g. `home`, `office`) that
+//! override a set of defaults.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use paths;
+
+/// Settings that can be set at the top level (as defaults) or per-profile.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Profile {
+    pub stop_id: Option<String>,
+    pub route: Option<String>,
+    pub destination: Option<String>,
+    pub accessible_only: Option<bool>,
+    pub format: Option<String>,
+    pub lang: Option<String>,
+    pub walk_minutes: Option<i64>,
+}
+
+impl Profile {
+    /// Overlay `other` on top of `self`, `other`'s values winning wherever
+    /// they're set.
+    fn merged_with(&self, other: &Profile) -> Profile {
+        Profile {
+            stop_id: other.stop_id.clone().or_else(|| self.stop_id.clone()),
+            route: other.route.clone().or_else(|| self.route.clone()),
+            destination: other.destination.clone().or_else(|| self.destination.clone()),
+            accessible_only: other.accessible_only.or(self.accessible_only),
+            format: other.format.clone().or_else(|| self.format.clone()),
+            lang: other.lang.clone().or_else(|| self.lang.clone()),
+            walk_minutes: other.walk_minutes.or(self.walk_minutes),
+        }
+    }
+}
+
+/// A parsed configuration file: defaults plus any number of named
+/// overriding profiles.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Config {
+    #[serde(default)]
+    pub defaults: Profile,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Error loading or parsing a configuration file.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Parse(e)
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref e) => write!(f, "{}", e),
+            ConfigError::Parse(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Config {
+    /// Parse a config file at `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let contents = fs::read_to_string(path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    /// Load from the XDG-compliant default config file location
+    /// ([`paths::config_file`]).
+    pub fn load_default() -> Result<Config, ConfigError> {
+        Config::load(paths::config_file())
+    }
+
+    /// The effective settings for `name`, with the named profile's values
+    /// overriding the defaults. Returns `None` if no such profile exists.
+    pub fn profile(&self, name: &str) -> Option<Profile> {
+        self.profiles.get(name).map(|p| self.defaults.merged_with(p))
+    }
+}
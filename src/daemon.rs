@@ -0,0 +1,141 @@
+//! Foundation for long-running poll loops (daemon/server modes): holds the
+//! set of watched stops behind a lock so it can be reloaded in place
+//! without restarting and losing warm caches.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use config::Config;
+use Departures;
+
+/// A cheaply-clonable handle to the most recently published board, plus a
+/// generation counter that increases on every publish — so a GUI consumer
+/// holding a `BoardSnapshot` across frames can tell without comparing the
+/// board itself whether a newer one has arrived.
+#[derive(Debug, Clone)]
+pub struct BoardSnapshot {
+    pub generation: u64,
+    pub board: Arc<Departures>,
+}
+
+fn reload_stops(stops: &Arc<Mutex<Vec<String>>>, config_path: &Path) {
+    if let Ok(config) = Config::load(config_path) {
+        let new_stops: Vec<String> = config.profiles.values().filter_map(|p| p.stop_id.clone()).collect();
+        if !new_stops.is_empty() {
+            *stops.lock().unwrap() = new_stops;
+        }
+    }
+}
+
+/// A long-running poller's set of watched stops, shareable across threads
+/// and reloadable without dropping any other state.
+pub struct Daemon {
+    stops: Arc<Mutex<Vec<String>>>,
+    latest: Arc<Mutex<Option<BoardSnapshot>>>,
+}
+
+impl Daemon {
+    pub fn new(stops: Vec<String>) -> Daemon {
+        Daemon { stops: Arc::new(Mutex::new(stops)), latest: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Publish a newly-polled board, bumping the generation counter.
+    /// Cheap: takes ownership of `board` and wraps it in a fresh `Arc`.
+    pub fn publish(&self, board: Departures) {
+        let mut latest = self.latest.lock().unwrap();
+        let generation = latest.as_ref().map_or(0, |s| s.generation + 1);
+        *latest = Some(BoardSnapshot { generation, board: Arc::new(board) });
+    }
+
+    /// The most recently published snapshot, if any. Clones are cheap
+    /// (an `Arc` bump), so GUI frameworks can hold one across frames
+    /// without deep-copying the board on every refresh.
+    pub fn snapshot(&self) -> Option<BoardSnapshot> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Snapshot of the currently watched stops.
+    pub fn stops(&self) -> Vec<String> {
+        self.stops.lock().unwrap().clone()
+    }
+
+    /// Start watching an additional stop.
+    pub fn add_stop(&self, stop_id: String) {
+        let mut stops = self.stops.lock().unwrap();
+        if !stops.contains(&stop_id) {
+            stops.push(stop_id);
+        }
+    }
+
+    /// Stop watching a stop.
+    pub fn remove_stop(&self, stop_id: &str) {
+        self.stops.lock().unwrap().retain(|s| s != stop_id);
+    }
+
+    /// Reload the stop set from every profile's `stop_id` in the config
+    /// file at `config_path` whenever the process receives SIGHUP. A no-op
+    /// on platforms without SIGHUP.
+    #[cfg(unix)]
+    pub fn reload_on_sighup(&self, config_path: PathBuf) {
+        let stops = Arc::clone(&self.stops);
+        let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGHUP])
+            .expect("failed to register SIGHUP handler");
+        std::thread::spawn(move || {
+            for _ in signals.forever() {
+                reload_stops(&stops, &config_path);
+            }
+        });
+    }
+
+    #[cfg(not(unix))]
+    pub fn reload_on_sighup(&self, _config_path: PathBuf) {}
+
+    /// Serve a line-based control protocol on a Unix domain socket at
+    /// `socket_path`: `add-stop ID`, `remove-stop ID`, and `list-stops`,
+    /// one command per connection. Lets a running daemon's watched stops
+    /// be changed (e.g. `kvvliveapi daemon add-stop de:8212:89`) without a
+    /// restart, which would otherwise lose all cached/recorded state.
+    #[cfg(unix)]
+    pub fn serve_control_socket(&self, socket_path: PathBuf) -> std::io::Result<()> {
+        use std::io::{BufRead, BufReader, Write};
+        use std::os::unix::net::UnixListener;
+
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path)?;
+        let stops = Arc::clone(&self.stops);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().filter_map(Result::ok) {
+                let mut writer = match stream.try_clone() {
+                    Ok(w) => w,
+                    Err(_) => continue,
+                };
+                let mut line = String::new();
+                if BufReader::new(stream).read_line(&mut line).is_err() {
+                    continue;
+                }
+
+                let mut parts = line.trim().splitn(2, ' ');
+                let response = match (parts.next(), parts.next()) {
+                    (Some("add-stop"), Some(id)) => {
+                        let mut stops = stops.lock().unwrap();
+                        if !stops.contains(&id.to_owned()) {
+                            stops.push(id.to_owned());
+                        }
+                        "ok\n".to_owned()
+                    }
+                    (Some("remove-stop"), Some(id)) => {
+                        stops.lock().unwrap().retain(|s| s != id);
+                        "ok\n".to_owned()
+                    }
+                    (Some("list-stops"), _) => format!("{}\n", stops.lock().unwrap().join(",")),
+                    _ => "error: unknown command\n".to_owned(),
+                };
+
+                let _ = writer.write_all(response.as_bytes());
+            }
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+//! Diffing consecutive boards for the same stop, powering watch-mode change
+//! highlighting (and reusable by anything else that cares what changed
+//! between two polls, such as cancellation detection).
+
+use Departure;
+use Departures;
+
+/// How a departure's row changed between two polls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// wasn't on the previous board
+    New,
+    /// predicted time moved earlier by this many minutes
+    Earlier(i64),
+    /// predicted time moved later by this many minutes
+    Later(i64),
+    /// was on the previous board, isn't on this one
+    Gone,
+    /// unchanged since the previous board
+    Unchanged,
+}
+
+/// A departure annotated with how it changed since the previous board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepartureChange {
+    pub departure: Departure,
+    pub change: Change,
+}
+
+fn matches(a: &Departure, b: &Departure) -> bool {
+    a.route == b.route && a.destination == b.destination && a.direction == b.direction
+}
+
+/// Diff `current` against `previous`, classifying every row still present
+/// or just-departed. Rows that vanished are included with [`Change::Gone`]
+/// and the departure as last seen.
+pub fn diff_boards(previous: &Departures, current: &Departures) -> Vec<DepartureChange> {
+    let mut changes: Vec<DepartureChange> = current
+        .departures
+        .iter()
+        .map(|dep| {
+            let change = match previous.departures.iter().find(|prev| matches(prev, dep)) {
+                None => Change::New,
+                Some(prev) => {
+                    let delta = dep.time.signed_duration_since(prev.time).num_minutes();
+                    if delta < 0 {
+                        Change::Earlier(-delta)
+                    } else if delta > 0 {
+                        Change::Later(delta)
+                    } else {
+                        Change::Unchanged
+                    }
+                }
+            };
+            DepartureChange { departure: dep.clone(), change }
+        })
+        .collect();
+
+    for prev in &previous.departures {
+        if !current.departures.iter().any(|cur| matches(prev, cur)) {
+            changes.push(DepartureChange { departure: prev.clone(), change: Change::Gone });
+        }
+    }
+
+    changes
+}
@@ -0,0 +1,114 @@
+//! Diagnostics for the handful of problems that account for most support
+//! requests: connectivity, API key validity, config file syntax, cache
+//! directory writability, and clock sanity. Driven by the CLI's `doctor`
+//! subcommand, but kept separate from `main.rs` so it can be unit-tested
+//! and reused by other front ends later.
+
+use std::fs;
+
+use clockskew;
+use config::{Config, ConfigError};
+use paths;
+use schema;
+
+/// The outcome of one diagnostic, for [`run`] to print and fold into the
+/// overall exit status.
+pub struct Check {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl Check {
+    fn pass(name: &str, detail: impl Into<String>) -> Check {
+        Check { name: name.to_owned(), ok: true, detail: detail.into() }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>) -> Check {
+        Check { name: name.to_owned(), ok: false, detail: detail.into() }
+    }
+}
+
+fn check_connectivity() -> Check {
+    match ::search_by_name("") {
+        Ok(_) => Check::pass("connectivity", "reached the API and the configured key was accepted"),
+        Err(e) => match e.status() {
+            Some(status) if status.as_u16() == 401 || status.as_u16() == 403 => {
+                Check::fail("connectivity", format!("API key was rejected ({})", status))
+            }
+            Some(status) => Check::fail("connectivity", format!("upstream returned {}", status)),
+            None if e.is_timeout() => Check::fail("connectivity", "request timed out before a response arrived"),
+            None => Check::fail("connectivity", format!("could not reach the API ({})", e)),
+        },
+    }
+}
+
+fn check_config() -> Check {
+    let path = paths::config_file();
+    match Config::load(&path) {
+        Ok(_) => Check::pass("config file", format!("{} parses OK", path.display())),
+        Err(ConfigError::Io(ref e)) if e.kind() == std::io::ErrorKind::NotFound => {
+            Check::pass("config file", format!("no config file at {} (using defaults)", path.display()))
+        }
+        Err(e) => Check::fail("config file", format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn check_cache_dir() -> Check {
+    let dir = paths::cache_dir();
+    let probe = dir.join(".doctor-write-test");
+    let result = fs::create_dir_all(&dir).and_then(|_| fs::write(&probe, b"ok")).and_then(|_| fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::pass("cache directory", format!("{} is writable", dir.display())),
+        Err(e) => Check::fail("cache directory", format!("{}: {}", dir.display(), e)),
+    }
+}
+
+/// Clock sanity is checked by comparing this machine's clock against a
+/// real board's `timestamp` via [`clockskew`] — which requires an actual
+/// departures fetch, so it's skipped (not failed) without a `stop_id` to
+/// probe.
+fn check_clock(stop_id: Option<&str>) -> Check {
+    let stop_id = match stop_id {
+        Some(id) => id,
+        None => return Check::pass("clock sanity", "skipped (pass a STOP_ID to check clock skew against a live board)"),
+    };
+    match ::departures_by_stop(stop_id) {
+        Ok(_) => {
+            let skew_secs = clockskew::current_skew().num_seconds();
+            if skew_secs.abs() > 60 {
+                Check::fail("clock sanity", format!("system clock is {}s off from the API's", skew_secs))
+            } else {
+                Check::pass("clock sanity", format!("within {}s of the API's clock", skew_secs))
+            }
+        }
+        Err(e) => Check::fail("clock sanity", format!("could not fetch {} to check: {}", stop_id, e)),
+    }
+}
+
+/// Checking schema drift, like clock sanity, requires an actual departures
+/// fetch, so it's skipped (not failed) without a `stop_id` to probe.
+fn check_schema(stop_id: Option<&str>) -> Check {
+    let stop_id = match stop_id {
+        Some(id) => id,
+        None => return Check::pass("API schema", "skipped (pass a STOP_ID to compare a live board against the known schema)"),
+    };
+    match ::query::<::serde_json::Value>(&format!("departures/bystop/{}", stop_id), vec![]) {
+        Ok(value) => {
+            let drifts = schema::check_departures(&value);
+            if drifts.is_empty() {
+                Check::pass("API schema", "matches what this crate expects")
+            } else {
+                Check::fail("API schema", format!("{} drift(s) from the known schema, e.g. {:?}", drifts.len(), drifts[0]))
+            }
+        }
+        Err(e) => Check::fail("API schema", format!("could not fetch {} to check: {}", stop_id, e)),
+    }
+}
+
+/// Run every diagnostic and return the results in a fixed, readable order.
+/// `stop_id` is an optional board to fetch for the clock sanity and schema
+/// checks — without one, those are reported as skipped rather than failed.
+pub fn run(stop_id: Option<&str>) -> Vec<Check> {
+    vec![check_connectivity(), check_config(), check_cache_dir(), check_clock(stop_id), check_schema(stop_id)]
+}
@@ -0,0 +1,87 @@
+//! An [`egui`] widget rendering a [`Departures`] board, for Rust desktop
+//! and embedded GUI apps that want a drop-in component instead of
+//! reimplementing row layout, line colors, and countdown formatting on top
+//! of [`viewmodel`](::viewmodel) themselves.
+//!
+//! Gated behind the `egui-widget` feature since it pulls in `egui`, which
+//! most consumers of this crate (a CLI, a headless daemon) don't need.
+
+use egui::{Color32, RichText, Ui, Widget};
+
+use viewmodel::{self, BoardView, DepartureRow};
+use Departures;
+
+fn hex_color(hex: &str) -> Color32 {
+    let hex = hex.trim_start_matches('#');
+    let r = u8::from_str_radix(&hex[0..2], 16).unwrap_or(0x55);
+    let g = u8::from_str_radix(&hex[2..4], 16).unwrap_or(0x55);
+    let b = u8::from_str_radix(&hex[4..6], 16).unwrap_or(0x55);
+    Color32::from_rgb(r, g, b)
+}
+
+/// A live departure board widget. Build one from a freshly fetched
+/// [`Departures`] every time you poll (e.g. from [`KvvClient`](::client::KvvClient)
+/// or a [`Typeahead`](::typeahead::Typeahead)-style background fetch) and
+/// hand it to `ui.add(...)` — it does no fetching of its own.
+pub struct DepartureBoard {
+    view: BoardView,
+    max_rows: Option<usize>,
+}
+
+impl DepartureBoard {
+    /// A widget for `board`, showing every departure.
+    pub fn new(board: &Departures) -> DepartureBoard {
+        DepartureBoard { view: viewmodel::view(board), max_rows: None }
+    }
+
+    /// Only show the first `max_rows` departures, e.g. to fit a small
+    /// kiosk display.
+    pub fn max_rows(mut self, max_rows: usize) -> DepartureBoard {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    fn row(&self, ui: &mut Ui, row: &DepartureRow) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&row.route).strong().background_color(hex_color(row.line_color)).color(Color32::WHITE));
+            ui.label(&row.destination);
+            if row.lowfloor {
+                ui.label("\u{267f}");
+            }
+            if let Some(occupancy) = row.occupancy {
+                ui.label(occupancy.glyph().to_string());
+            }
+            if row.is_last_before_night_service {
+                ui.label(RichText::new("last tonight").small().color(Color32::GRAY));
+            }
+            let mut countdown = RichText::new(&row.countdown);
+            if !row.realtime {
+                countdown = countdown.color(Color32::GRAY);
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.label(countdown);
+            });
+        });
+    }
+}
+
+impl Widget for DepartureBoard {
+    fn ui(self, ui: &mut Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            ui.heading(&self.view.stop_name);
+            ui.label(RichText::new(format!("as of {}", self.view.as_of)).small().color(Color32::GRAY));
+            ui.separator();
+            let rows = match self.max_rows {
+                Some(n) => &self.view.rows[..n.min(self.view.rows.len())],
+                None => &self.view.rows[..],
+            };
+            for row in rows {
+                // `row_id` is stable across refreshes of the same
+                // departure, so a future animated version of this widget
+                // can key transitions on it instead of the row index.
+                ui.push_id(&row.row_id, |ui| self.row(ui, row));
+            }
+        })
+        .response
+    }
+}
@@ -0,0 +1,53 @@
+//! A typed view of this crate's REST endpoints, for advanced callers who
+//! want to build a request URL themselves — e.g. to hand it to their own
+//! HTTP client, or just to log it — instead of going through
+//! [`KvvClient`](::client::KvvClient) or the free functions.
+//!
+//! [`to_url`](Endpoint::to_url) percent-encodes path parameters (stop
+//! names, routes) via [`Url::path_segments_mut`] rather than formatting
+//! them into the path by hand, so a stop name like "Karlsruhe
+//! Hauptbahnhof Süd" round-trips correctly instead of producing a URL
+//! that only looks valid for names without spaces or umlauts.
+
+use url::Url;
+
+/// One of the KVV live API's REST endpoints, with its path parameters as
+/// typed fields. Doesn't carry the API key — pass it to
+/// [`to_url`](Endpoint::to_url).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Endpoint<'a> {
+    DeparturesByStop { stop_id: &'a str },
+    DeparturesByRoute { stop_id: &'a str, route: &'a str },
+    SearchByName { name: &'a str },
+    SearchByLatLon { lat: f64, lon: f64 },
+    SearchByStopId { stop_id: &'a str },
+}
+
+impl<'a> Endpoint<'a> {
+    /// Build the request URL for this endpoint against `base_url` (e.g.
+    /// [`EfaPreset::base_url`](::preset::EfaPreset::base_url)), with
+    /// `key` attached as the `key` query parameter.
+    ///
+    /// Returns `Err` only if `base_url` itself doesn't parse as a URL
+    /// that can have path segments appended (e.g. it's empty or not
+    /// hierarchical) — this crate's own presets never hit that case.
+    pub fn to_url(&self, base_url: &str, key: &str) -> Result<Url, url::ParseError> {
+        let mut url = Url::parse(base_url)?;
+        {
+            let lat_lon;
+            let segments: &[&str] = match *self {
+                Endpoint::DeparturesByStop { stop_id } => &["departures", "bystop", stop_id],
+                Endpoint::DeparturesByRoute { stop_id, route } => &["departures", "byroute", route, stop_id],
+                Endpoint::SearchByName { name } => &["stops", "byname", name],
+                Endpoint::SearchByLatLon { lat, lon } => {
+                    lat_lon = [lat.to_string(), lon.to_string()];
+                    &["stops", "bylatlon", &lat_lon[0], &lat_lon[1]]
+                }
+                Endpoint::SearchByStopId { stop_id } => &["stops", "bystop", stop_id],
+            };
+            url.path_segments_mut().map_err(|_| url::ParseError::RelativeUrlWithoutBase)?.pop_if_empty().extend(segments);
+        }
+        url.query_pairs_mut().append_pair("key", key);
+        Ok(url)
+    }
+}
@@ -0,0 +1,47 @@
+//! A process-wide hook called on every failed upstream request, so
+//! embedders can forward failures to Sentry or their own alerting without
+//! wrapping every call site in the crate.
+
+use std::sync::{Mutex, OnceLock};
+
+/// Context about one failed request, passed to the registered hook.
+#[derive(Debug, Clone)]
+pub struct ErrorContext {
+    /// the endpoint path that was queried, e.g. `"departures/bystop/..."`
+    pub endpoint: String,
+    /// which attempt this was, starting at 1
+    pub attempt: u32,
+    /// the HTTP status code, if the request got that far
+    pub status: Option<u16>,
+    /// a snippet of the raw response body, when available
+    ///
+    /// Currently always `None`: the client doesn't keep the raw body
+    /// around once the typed response fails to parse.
+    pub body_snippet: Option<String>,
+}
+
+type Hook = Box<dyn Fn(&ErrorContext) + Send + Sync>;
+
+fn hook_slot() -> &'static Mutex<Option<Hook>> {
+    static HOOK: OnceLock<Mutex<Option<Hook>>> = OnceLock::new();
+    HOOK.get_or_init(|| Mutex::new(None))
+}
+
+/// Register a callback invoked for every failed request in this process.
+/// Replaces any previously registered hook.
+pub fn set_error_hook<F: Fn(&ErrorContext) + Send + Sync + 'static>(hook: F) {
+    *hook_slot().lock().unwrap() = Some(Box::new(hook));
+}
+
+/// Remove the currently registered hook, if any.
+pub fn clear_error_hook() {
+    *hook_slot().lock().unwrap() = None;
+}
+
+/// Invoke the registered hook, if any, with `context`. Used internally by
+/// the client after every failed attempt.
+pub fn report(context: ErrorContext) {
+    if let Some(ref hook) = *hook_slot().lock().unwrap() {
+        hook(&context);
+    }
+}
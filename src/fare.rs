@@ -0,0 +1,186 @@
+//! Mapping stops to KVV fare zones ("Waben") so callers can answer "which
+//! Wabe is this stop in" and "how many Waben does this trip cross" without
+//! maintaining that table themselves.
+//!
+//! KVV publishes the stop-to-Wabe assignment as open data, but the exact
+//! export schema isn't pinned down here; this module loads a simple
+//! `stop_id;zone` CSV (with a header row, column names matched
+//! case-insensitively) from wherever the caller points it, defaulting to
+//! [`paths::fare_zones_file`](::paths::fare_zones_file). A stop can belong
+//! to more than one zone near a boundary, so each stop maps to a list.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Maps stop ids to the fare zone(s) ("Waben") they belong to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZoneMap {
+    zones_by_stop: HashMap<String, Vec<String>>,
+}
+
+/// Errors loading a fare zone CSV.
+#[derive(Debug)]
+pub enum FareError {
+    Io(io::Error),
+    MissingColumn(String),
+}
+
+impl fmt::Display for FareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FareError::Io(ref e) => write!(f, "io error: {}", e),
+            FareError::MissingColumn(ref col) => write!(f, "fare zone CSV is missing expected column \"{}\"", col),
+        }
+    }
+}
+
+impl From<io::Error> for FareError {
+    fn from(e: io::Error) -> FareError {
+        FareError::Io(e)
+    }
+}
+
+impl ZoneMap {
+    /// Load a `stop_id;zone` CSV (semicolon-delimited, header row
+    /// required). A stop id may appear on more than one row if it
+    /// belongs to multiple zones.
+    pub fn load_csv<P: AsRef<Path>>(path: P) -> Result<ZoneMap, FareError> {
+        let contents = fs::read_to_string(path)?;
+        let mut lines = contents.lines();
+
+        let header: Vec<&str> = lines.next().unwrap_or("").split(';').map(|s| s.trim()).collect();
+        let stop_col = header
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case("stop_id"))
+            .ok_or_else(|| FareError::MissingColumn("stop_id".to_owned()))?;
+        let zone_col = header
+            .iter()
+            .position(|c| c.eq_ignore_ascii_case("zone"))
+            .ok_or_else(|| FareError::MissingColumn("zone".to_owned()))?;
+
+        let mut zones_by_stop: HashMap<String, Vec<String>> = HashMap::new();
+        for line in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.split(';').collect();
+            let (stop_id, zone) = match (fields.get(stop_col), fields.get(zone_col)) {
+                (Some(s), Some(z)) if !s.trim().is_empty() && !z.trim().is_empty() => (s.trim().to_owned(), z.trim().to_owned()),
+                _ => continue,
+            };
+            zones_by_stop.entry(stop_id).or_default().push(zone);
+        }
+
+        Ok(ZoneMap { zones_by_stop })
+    }
+
+    /// The zone(s) a stop belongs to, if known.
+    pub fn zones_for(&self, stop_id: &str) -> &[String] {
+        self.zones_by_stop.get(stop_id).map(|v| v.as_slice()).unwrap_or(&[])
+    }
+
+    /// All zones spanned by a trip between two stops, as the union of
+    /// each stop's zones.
+    ///
+    /// This is the simplest possible notion of "zones crossed" and
+    /// doesn't account for the zones of intermediate stops on routes
+    /// that briefly dip through a zone neither endpoint touches; doing
+    /// that needs the full route geometry, which this crate doesn't have.
+    pub fn fare_zones_between(&self, stop_a: &str, stop_b: &str) -> Vec<String> {
+        let mut zones: Vec<String> = self.zones_for(stop_a).to_vec();
+        for zone in self.zones_for(stop_b) {
+            if !zones.contains(zone) {
+                zones.push(zone.clone());
+            }
+        }
+        zones
+    }
+}
+
+/// The kind of ticket to estimate a fare for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicketType {
+    /// "Einzelfahrt": single trip, priced by the number of Waben crossed.
+    Einzelfahrt,
+    /// "Tageskarte": unlimited travel for the day within the spanned Waben.
+    Tageskarte,
+}
+
+impl TicketType {
+    fn german_name(&self) -> &'static str {
+        match *self {
+            TicketType::Einzelfahrt => "Einzelfahrt",
+            TicketType::Tageskarte => "Tageskarte",
+        }
+    }
+}
+
+/// A guess at the ticket a rider would need, derived only from the
+/// number of Waben spanned between two stops.
+///
+/// This is **not** an official price quote: it doesn't know about
+/// discounted fares, youth/senior tickets, or promotional pricing, and
+/// ticket naming/pricing can change. Treat it as a starting point, not
+/// something to act on without checking the current tariff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FareEstimate {
+    /// e.g. `"Einzelfahrt 2 Waben"`
+    pub ticket_name: String,
+    pub zones: Vec<String>,
+}
+
+/// Guess the ticket needed to travel between two stops, based only on
+/// how many Waben the trip spans (see [`ZoneMap::fare_zones_between`]).
+///
+/// Returns `None` if either stop's zone is unknown.
+pub fn estimate_fare(zone_map: &ZoneMap, stop_a: &str, stop_b: &str, ticket_type: TicketType) -> Option<FareEstimate> {
+    if zone_map.zones_for(stop_a).is_empty() || zone_map.zones_for(stop_b).is_empty() {
+        return None;
+    }
+    let zones = zone_map.fare_zones_between(stop_a, stop_b);
+    let zone_count = zones.len().max(1);
+    Some(FareEstimate {
+        ticket_name: format!("{} {} Wabe{}", ticket_type.german_name(), zone_count, if zone_count == 1 { "" } else { "n" }),
+        zones,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> ZoneMap {
+        let mut zones_by_stop = HashMap::new();
+        zones_by_stop.insert("a".to_owned(), vec!["1".to_owned()]);
+        zones_by_stop.insert("b".to_owned(), vec!["1".to_owned(), "2".to_owned()]);
+        zones_by_stop.insert("c".to_owned(), vec!["3".to_owned()]);
+        ZoneMap { zones_by_stop }
+    }
+
+    #[test]
+    fn fare_zones_between_unions_without_duplicates() {
+        let zones = map().fare_zones_between("a", "b");
+        assert_eq!(zones, vec!["1".to_owned(), "2".to_owned()]);
+    }
+
+    #[test]
+    fn fare_zones_between_unknown_stop_is_just_the_known_one() {
+        let zones = map().fare_zones_between("a", "unknown");
+        assert_eq!(zones, vec!["1".to_owned()]);
+    }
+
+    #[test]
+    fn estimate_fare_counts_distinct_zones_spanned() {
+        let estimate = estimate_fare(&map(), "a", "c", TicketType::Einzelfahrt).unwrap();
+        assert_eq!(estimate.ticket_name, "Einzelfahrt 2 Waben");
+        assert_eq!(estimate.zones, vec!["1".to_owned(), "3".to_owned()]);
+    }
+
+    #[test]
+    fn estimate_fare_is_none_for_an_unknown_stop() {
+        assert_eq!(estimate_fare(&map(), "a", "unknown", TicketType::Einzelfahrt), None);
+    }
+}
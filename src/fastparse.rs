@@ -0,0 +1,22 @@
+//! Optional simd-accelerated JSON parsing for high-throughput callers
+//! (e.g. a [`daemon`](::daemon) polling hundreds of boards a minute),
+//! behind the `simd-json` feature. `simd_json::from_slice` parses in
+//! place over a mutable byte buffer instead of `serde_json::from_str`'s
+//! intermediate `String` allocation per field.
+//!
+//! This operates on raw bytes rather than reqwest's blocking
+//! `Response::json()` helper (which owns its own deserialization path),
+//! so it's meant for callers that already read the response body
+//! themselves — e.g. a daemon loop that reuses one `Vec<u8>` across
+//! many polls instead of allocating fresh per board.
+
+use Departures;
+
+/// Parse a single board from a mutable JSON byte buffer using simd-json.
+///
+/// `bytes` is mutated in place by the parser (simd-json requires this);
+/// reuse the same buffer across calls, clearing and refilling it, to
+/// avoid a fresh allocation per board.
+pub fn parse_departures(bytes: &mut [u8]) -> Result<Departures, simd_json::Error> {
+    simd_json::from_slice(bytes)
+}
@@ -0,0 +1,114 @@
+//! A C ABI for this crate, behind the `ffi` feature, so non-Rust
+//! embedded/firmware code (e.g. an info-display board) can drive a
+//! [`KvvClient`] without linking against Rust. Every call hands back a
+//! JSON string (`{"ok":true,"data":...}` or `{"ok":false,"error":"..."}`)
+//! rather than a C struct, so callers only need to know JSON, not track
+//! this crate's Rust layout across versions. A C header for this module
+//! can be generated with `cbindgen` (see `cbindgen.toml`).
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+use serde::Serialize;
+use serde_json::json;
+
+use client::{ClientError, KvvClient};
+
+/// An opaque handle to a [`KvvClient`], owned by the C caller and freed
+/// with [`kvv_client_free`].
+pub struct KvvClientHandle(KvvClient);
+
+/// Create a client preconfigured for the KVV (Karlsruhe) network. Free it
+/// with [`kvv_client_free`] once done.
+#[no_mangle]
+pub extern "C" fn kvv_client_new() -> *mut KvvClientHandle {
+    Box::into_raw(Box::new(KvvClientHandle(KvvClient::kvv())))
+}
+
+/// Free a client created with [`kvv_client_new`]. Passing `NULL` is a
+/// no-op; passing a pointer not returned by `kvv_client_new`, or freeing
+/// one twice, is undefined behavior.
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a pointer previously returned by
+/// [`kvv_client_new`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn kvv_client_free(handle: *mut KvvClientHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+unsafe fn c_str_to_string(s: *const c_char) -> Option<String> {
+    if s.is_null() {
+        return None;
+    }
+    CStr::from_ptr(s).to_str().ok().map(|s| s.to_owned())
+}
+
+fn result_to_json<T: Serialize>(result: Result<T, ClientError>) -> String {
+    match result {
+        Ok(value) => json!({"ok": true, "data": value}).to_string(),
+        Err(e) => json!({"ok": false, "error": e.to_string()}).to_string(),
+    }
+}
+
+fn string_to_c(s: String) -> *mut c_char {
+    CString::new(s).map(CString::into_raw).unwrap_or(ptr::null_mut())
+}
+
+/// Search for stops by name, returning a JSON-encoded result string (see
+/// module docs) to be freed with [`kvv_string_free`]. Returns `NULL` if
+/// `handle` or `name` is `NULL`, or `name` isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `handle` must be a valid pointer returned by [`kvv_client_new`], and
+/// `name`, if non-`NULL`, must point to a NUL-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn kvv_search_by_name(handle: *const KvvClientHandle, name: *const c_char) -> *mut c_char {
+    if handle.is_null() {
+        return ptr::null_mut();
+    }
+    let name = match c_str_to_string(name) {
+        Some(name) => name,
+        None => return ptr::null_mut(),
+    };
+    string_to_c(result_to_json((*handle).0.search_by_name(&name)))
+}
+
+/// Fetch departures for a stop id, returning a JSON-encoded result string
+/// (see module docs) to be freed with [`kvv_string_free`]. Returns `NULL`
+/// if `handle` or `stop_id` is `NULL`, or `stop_id` isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `handle` must be a valid pointer returned by [`kvv_client_new`], and
+/// `stop_id`, if non-`NULL`, must point to a NUL-terminated C string.
+#[no_mangle]
+pub unsafe extern "C" fn kvv_departures_by_stop(handle: *const KvvClientHandle, stop_id: *const c_char) -> *mut c_char {
+    if handle.is_null() {
+        return ptr::null_mut();
+    }
+    let stop_id = match c_str_to_string(stop_id) {
+        Some(stop_id) => stop_id,
+        None => return ptr::null_mut(),
+    };
+    string_to_c(result_to_json((*handle).0.departures_by_stop(&stop_id)))
+}
+
+/// Free a string returned by any `kvv_*` function. Passing `NULL` is a
+/// no-op.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a pointer previously returned by one of this
+/// module's functions, and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn kvv_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,50 @@
+//! Rendering the departures an [`AlertRule`](::rules::AlertRule) matches
+//! as an iCal feed, so a calendar app can show "S2 07:43" in today's
+//! agenda for a recurring commute without the user doing anything beyond
+//! subscribing to a URL once.
+//!
+//! Like [`atom`](::atom), this only builds the feed text; serving it at a
+//! per-user URL, refreshed on every fetch, is an HTTP server's job, which
+//! this crate doesn't have yet.
+
+use std::fmt::Write as _;
+
+use rules::{self, AlertRule};
+use {Departure, Departures};
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace(',', "\\,").replace(';', "\\;").replace('\n', "\\n")
+}
+
+fn event(departure: &Departure, stop_id: &str, uid_suffix: &str) -> String {
+    let mut event = String::new();
+    writeln!(event, "BEGIN:VEVENT").unwrap();
+    writeln!(event, "UID:{}-{}-{}@kvvliveapi", stop_id, departure.time.timestamp(), uid_suffix).unwrap();
+    writeln!(event, "DTSTAMP:{}", departure.time.format("%Y%m%dT%H%M%SZ")).unwrap();
+    writeln!(event, "DTSTART:{}", departure.time.format("%Y%m%dT%H%M%S")).unwrap();
+    writeln!(event, "SUMMARY:{} to {}", escape(&departure.route), escape(&departure.destination.terminus)).unwrap();
+    writeln!(event, "END:VEVENT").unwrap();
+    event
+}
+
+/// Render the departures `rule` currently matches on `board` as an iCal
+/// feed, one `VEVENT` per departure.
+///
+/// Call this again on every fetch of the feed URL to pick up the latest
+/// board; there's no caching here, matching [`rules::evaluate`]'s own
+/// "evaluate fresh each poll" design.
+pub fn commute_feed(rule: &AlertRule, stop_id: &str, now: ::chrono::DateTime<::chrono_tz::Tz>, board: &Departures) -> String {
+    let mut ical = String::new();
+    writeln!(ical, "BEGIN:VCALENDAR").unwrap();
+    writeln!(ical, "VERSION:2.0").unwrap();
+    writeln!(ical, "PRODID:-//kvvliveapi//commute feed//EN").unwrap();
+
+    if rule.is_active(now) {
+        for departure in rules::evaluate(rule, stop_id, now, board) {
+            ical.push_str(&event(departure, stop_id, "commute"));
+        }
+    }
+
+    writeln!(ical, "END:VCALENDAR").unwrap();
+    ical
+}
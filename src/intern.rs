@@ -0,0 +1,47 @@
+//! An optional string-interning cache for route and destination names.
+//!
+//! A long-running recording daemon sees the same handful of route and
+//! destination strings over and over; interning them into a shared
+//! `Arc<str>` means repeated observations share one allocation instead of
+//! accumulating a fresh `String` per poll.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use Departure;
+
+fn pool() -> &'static Mutex<HashSet<Arc<str>>> {
+    static POOL: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+    POOL.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Return a shared `Arc<str>` for `s`, reusing a previously interned copy
+/// if one already exists in the process-wide pool.
+pub fn intern(s: &str) -> Arc<str> {
+    let mut pool = pool().lock().unwrap();
+    if let Some(existing) = pool.get(s) {
+        return existing.clone();
+    }
+    let arc: Arc<str> = Arc::from(s);
+    pool.insert(arc.clone());
+    arc
+}
+
+/// Number of distinct strings currently interned, mostly useful to verify
+/// the pool is actually deduplicating in a long-running process.
+pub fn pool_size() -> usize {
+    pool().lock().unwrap().len()
+}
+
+impl Departure {
+    /// This departure's route, interned into the shared string pool.
+    pub fn interned_route(&self) -> Arc<str> {
+        intern(&self.route)
+    }
+
+    /// This departure's destination terminus, interned into the shared
+    /// string pool.
+    pub fn interned_destination(&self) -> Arc<str> {
+        intern(&self.destination.terminus)
+    }
+}
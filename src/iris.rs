@@ -0,0 +1,66 @@
+//! Merging long-distance mainline departures (ICE/IC/RE, from DB's IRIS
+//! timetable API) into a tram board, so a single display can show both.
+//!
+//! Karlsruhe Hbf is served by both the tram network and DB's long-distance
+//! and regional trains, and IRIS is a separate system from the KVV live
+//! API: different station identifiers (EVA numbers, not KVV stop ids),
+//! different schema, and its own auth. Fetching from IRIS itself isn't
+//! implemented yet ([`fetch`] is a stub) since that needs its own XML
+//! client and this crate hasn't integrated against it; the part that *is*
+//! done is the board-merging logic below, which is what actually matters
+//! for a combined display.
+
+use chrono::DateTime;
+use chrono_tz::Tz;
+
+use Departure;
+use Departures;
+
+/// A single long-distance/regional mainline departure from IRIS.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MainlineDeparture {
+    /// train category, e.g. `"ICE"`, `"IC"`, `"RE"`
+    pub category: String,
+    /// line/train number, e.g. `"593"`
+    pub line: String,
+    pub destination: String,
+    pub time: DateTime<Tz>,
+    pub platform: Option<String>,
+    /// delay against schedule in minutes, if known
+    pub delay_minutes: Option<i64>,
+}
+
+/// One entry in a combined board: either a tram departure from the KVV
+/// live API or a mainline departure from IRIS.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CombinedEntry {
+    Tram(Departure),
+    Mainline(MainlineDeparture),
+}
+
+/// Merge a tram board with mainline departures, sorted by departure time.
+pub fn merge(trams: &Departures, mainline: &[MainlineDeparture]) -> Vec<CombinedEntry> {
+    let mut combined: Vec<CombinedEntry> = Vec::with_capacity(trams.departures.len() + mainline.len());
+    combined.extend(trams.departures.iter().cloned().map(CombinedEntry::Tram));
+    combined.extend(mainline.iter().cloned().map(CombinedEntry::Mainline));
+    combined.sort_by_key(|entry| match entry {
+        CombinedEntry::Tram(d) => d.time,
+        CombinedEntry::Mainline(d) => d.time,
+    });
+    combined
+}
+
+/// Error fetching from DB's IRIS timetable API.
+#[derive(Debug)]
+pub enum IrisError {
+    /// IRIS fetching isn't implemented yet; see the module docs.
+    NotImplemented,
+}
+
+/// Fetch mainline departures from IRIS for the given station EVA id (e.g.
+/// `8000191` for Karlsruhe Hbf).
+///
+/// Not yet implemented, see the module docs.
+pub fn fetch(_eva_id: &str) -> Result<Vec<MainlineDeparture>, IrisError> {
+    Err(IrisError::NotImplemented)
+}
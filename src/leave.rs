@@ -0,0 +1,125 @@
+//! "When do I have to leave" — combining a walking time to the stop with
+//! the board to find the latest moment you can still leave home and
+//! catch a particular line.
+
+use chrono::{DateTime, Duration};
+use chrono_tz::Tz;
+
+use Departures;
+
+/// Average walking speed used to turn a distance into a rough walking
+/// time, in km/h.
+const WALKING_SPEED_KMH: f64 = 5.0;
+
+fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (lon2 - lon1).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    EARTH_RADIUS_KM * 2.0 * a.sqrt().asin()
+}
+
+/// Estimate the walking time between two points as the crow flies, in
+/// minutes, rounded up.
+///
+/// This ignores actual streets and paths, so it's a lower bound at best;
+/// prefer a known walking time (e.g. `--walk 6m`) when you have one.
+pub fn estimate_walk_minutes(from_lat: f64, from_lon: f64, to_lat: f64, to_lon: f64) -> i64 {
+    let distance_km = haversine_km(from_lat, from_lon, to_lat, to_lon);
+    (distance_km / WALKING_SPEED_KMH * 60.0).ceil() as i64
+}
+
+/// A departure you could still catch, and the latest moment to leave
+/// home for it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaveTime {
+    pub route: String,
+    pub destination: String,
+    pub departure_time: DateTime<Tz>,
+    pub leave_at: DateTime<Tz>,
+}
+
+/// The soonest departure (optionally filtered by route/destination)
+/// you can still catch by leaving `walk_minutes` from now, and the
+/// latest moment to leave for it.
+///
+/// Departures you could only catch by leaving in the past are skipped,
+/// not returned with a negative leave time.
+pub fn next_leave_time(board: &Departures, route: Option<&str>, destination: Option<&str>, walk_minutes: i64, now: DateTime<Tz>) -> Option<LeaveTime> {
+    board
+        .departures
+        .iter()
+        .filter(|d| route.is_none_or(|r| d.route.eq_ignore_ascii_case(r)))
+        .filter(|d| destination.is_none_or(|dest| d.destination.terminus.eq_ignore_ascii_case(dest)))
+        .filter_map(|d| {
+            let leave_at = d.time - Duration::minutes(walk_minutes);
+            if leave_at >= now {
+                Some(LeaveTime { route: d.route.clone(), destination: d.destination.terminus.clone(), departure_time: d.time, leave_at })
+            } else {
+                None
+            }
+        })
+        .min_by_key(|leave| leave.departure_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+    use {Departure, Destination};
+
+    fn at(minute: u32) -> DateTime<Tz> {
+        Berlin.with_ymd_and_hms(2026, 8, 9, 12, minute, 0).unwrap()
+    }
+
+    fn departure(route: &str, destination: &str, minute: u32) -> Departure {
+        Departure::new(route, Destination::new(destination, Vec::new(), destination), "1", at(minute), false, true, 0)
+    }
+
+    fn board(departures: Vec<Departure>) -> Departures {
+        Departures::new(at(0), "Test", departures)
+    }
+
+    #[test]
+    fn haversine_between_identical_points_is_zero_minutes() {
+        assert_eq!(estimate_walk_minutes(49.0, 8.4, 49.0, 8.4), 0);
+    }
+
+    #[test]
+    fn estimate_walk_minutes_rounds_up() {
+        // Roughly 1km apart along a line of longitude at this latitude, at
+        // 5 km/h that's 12 minutes — round up from a fraction over 11.
+        let minutes = estimate_walk_minutes(49.0, 8.4, 49.009, 8.4);
+        assert!(minutes >= 11 && minutes <= 13, "expected ~12 minutes, got {}", minutes);
+    }
+
+    #[test]
+    fn picks_the_soonest_departure_still_catchable() {
+        let board = board(vec![departure("S2", "Rheinstetten", 5), departure("S2", "Rheinstetten", 20)]);
+        let result = next_leave_time(&board, None, None, 3, at(0)).unwrap();
+        assert_eq!(result.departure_time, at(5));
+        assert_eq!(result.leave_at, at(2));
+    }
+
+    #[test]
+    fn skips_departures_that_would_require_leaving_in_the_past() {
+        let board = board(vec![departure("S2", "Rheinstetten", 5), departure("S2", "Rheinstetten", 20)]);
+        let result = next_leave_time(&board, None, None, 10, at(0)).unwrap();
+        assert_eq!(result.departure_time, at(20));
+    }
+
+    #[test]
+    fn filters_by_route_and_destination() {
+        let board = board(vec![departure("S1", "Hochstetten", 5), departure("S2", "Rheinstetten", 10)]);
+        let result = next_leave_time(&board, Some("s2"), Some("rheinstetten"), 0, at(0)).unwrap();
+        assert_eq!(result.route, "S2");
+    }
+
+    #[test]
+    fn none_when_nothing_is_catchable() {
+        let board = board(vec![departure("S2", "Rheinstetten", 5)]);
+        assert_eq!(next_leave_time(&board, None, None, 30, at(0)), None);
+    }
+}
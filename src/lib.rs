@@ -8,6 +8,92 @@ extern crate chrono_tz;
 extern crate regex;
 extern crate url;
 extern crate reqwest;
+extern crate unicode_segmentation;
+extern crate rusqlite;
+extern crate toml;
+extern crate directories;
+extern crate terminal_size;
+extern crate qrcode;
+extern crate image;
+#[cfg(feature = "png-render")]
+extern crate imageproc;
+#[cfg(feature = "png-render")]
+extern crate ab_glyph;
+#[cfg(feature = "time-compat")]
+extern crate time;
+#[cfg(feature = "simd-json")]
+extern crate simd_json;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "server")]
+extern crate tiny_http;
+#[cfg(feature = "egui-widget")]
+extern crate egui;
+#[cfg(unix)]
+extern crate signal_hook;
+
+pub mod abbreviate;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod atom;
+#[cfg(feature = "png-render")]
+pub mod board_png;
+pub mod cache;
+pub mod cancellation;
+pub mod client;
+pub mod clockskew;
+pub mod config;
+pub mod daemon;
+pub mod diff;
+pub mod doctor;
+#[cfg(feature = "egui-widget")]
+pub mod egui_widget;
+pub mod endpoint;
+pub mod error_hook;
+pub mod fare;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "simd-json")]
+pub mod fastparse;
+pub mod ical;
+pub mod intern;
+pub mod iris;
+pub mod leave;
+pub mod linemeta;
+pub mod locale;
+pub mod logging;
+pub mod map;
+pub mod metrics;
+pub mod nextbike;
+pub mod nlquery;
+pub mod notify;
+pub mod paths;
+pub mod preset;
+pub mod qr;
+pub mod ranking;
+pub mod raw;
+pub mod recorder;
+pub mod rpc;
+pub mod rules;
+pub mod schedule;
+pub mod schema;
+pub mod scheduler;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod smoothing;
+pub mod staticmap;
+pub mod stats;
+pub mod speech;
+pub mod svg;
+pub mod term;
+pub mod typeahead;
+#[cfg(feature = "time-compat")]
+pub mod timecompat;
+pub mod transfer;
+pub mod vcr;
+pub mod vehicleposition;
+pub mod viewmodel;
+pub mod zhv;
 
 use chrono::{NaiveDateTime, NaiveTime, DateTime, Local, Duration, TimeZone};
 use chrono_tz::Europe::Berlin;
@@ -18,27 +104,27 @@ use reqwest::{Client, StatusCode};
 
 use std::str::FromStr;
 use std::fmt::Display;
-
-const API_KEY: &str = "377d840e54b59adbe53608ba1aad70e8";
-const API_BASE: &str = "https://live.kvv.de/webapp/";
-
-fn parse_departure_time<'de, D>(deserializer: D) -> Result<DateTime<chrono_tz::Tz>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let s: String = Deserialize::deserialize(deserializer)?;
-
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+/// Parse a raw departure-time string as the live API sends it: the
+/// literal `"0"`, a single-digit `"N min"` countdown, or a bare 24-hour
+/// `"HH:MM"` clock time (rolled to tomorrow if it's already in the
+/// past). Split out from [`parse_departure_time`] so it can be fuzzed
+/// directly — see `fuzz/fuzz_targets/departure_time.rs` — without going
+/// through a `Deserializer`.
+pub fn parse_departure_time_str(s: &str) -> Result<DateTime<chrono_tz::Tz>, String> {
     let re = Regex::new(r"^([1-9]) min$").unwrap();
 
     if s == "0" {
         Ok(Local::now().with_timezone(&Berlin))
-    } else if re.is_match(&s) {
+    } else if re.is_match(s) {
         // unwraps should be ok, because of the regex test
-        let mins = &re.captures_iter(&s).nth(0).unwrap()[1];
+        let mins = &re.captures_iter(s).nth(0).unwrap()[1];
         let mins = i64::from_str(mins).unwrap();
         Ok(Local::now().with_timezone(&Berlin) + Duration::minutes(mins))
     } else {
-        NaiveTime::parse_from_str(&s, "%H:%M")
+        NaiveTime::parse_from_str(s, "%H:%M")
             .map(|t| {
                 let now = Local::now().with_timezone(&Berlin).naive_local();
                 let mut departure = now.date().and_time(t);
@@ -47,31 +133,128 @@ where
                 }
                 Berlin.from_local_datetime(&departure).unwrap()
             })
-            .map_err(serde::de::Error::custom)
+            .map_err(|e| e.to_string())
     }
 }
 
-fn parse_timestamp<'de, D>(deserializer: D) -> Result<DateTime<chrono_tz::Tz>, D::Error>
+fn parse_departure_time<'de, D>(deserializer: D) -> Result<DateTime<chrono_tz::Tz>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: String = Deserialize::deserialize(deserializer)?;
-    NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+    parse_departure_time_str(&s).map_err(serde::de::Error::custom)
+}
+
+/// Parse a raw response timestamp string (`"%Y-%m-%d %H:%M:%S"`). Split
+/// out from [`parse_timestamp`] so it can be fuzzed directly — see
+/// `fuzz/fuzz_targets/timestamp.rs` — without going through a
+/// `Deserializer`.
+pub fn parse_timestamp_str(s: &str) -> Result<DateTime<chrono_tz::Tz>, String> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
         .map(|d| Berlin.from_local_datetime(&d).unwrap())
-        .map_err(serde::de::Error::custom)
+        .map_err(|e| e.to_string())
+}
+
+fn parse_timestamp<'de, D>(deserializer: D) -> Result<DateTime<chrono_tz::Tz>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    parse_timestamp_str(&s).map_err(serde::de::Error::custom)
+}
+
+/// A parsed destination, separating the actual terminus from any "via"
+/// stops the API folds into the same string (e.g.
+/// `"Durlach über Tullastraße"`).
+///
+/// `#[non_exhaustive]`: construct one with [`Destination::new`] so a
+/// later field doesn't break downstream struct literals.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[non_exhaustive]
+pub struct Destination {
+    /// the terminus, with any " über ..." suffix stripped
+    pub terminus: String,
+    /// via stops, in order, if the API mentioned any
+    pub via: Vec<String>,
+    /// the original, unparsed destination string
+    pub raw: String,
+}
+
+impl Destination {
+    /// Build a destination directly from already-separated parts,
+    /// without going through [`Destination::parse`]'s " über " splitting
+    /// — e.g. for a [`Departure`] built by hand rather than deserialized.
+    pub fn new(terminus: &str, via: Vec<String>, raw: &str) -> Destination {
+        Destination { terminus: terminus.to_owned(), via, raw: raw.to_owned() }
+    }
+
+    fn parse(raw: &str) -> Destination {
+        let mut parts = raw.split(" über ");
+        let terminus = parts.next().unwrap_or(raw).trim().to_owned();
+        let via = parts.next().map_or_else(Vec::new, |rest| {
+            rest.split(',').map(|v| v.trim().to_owned()).collect()
+        });
+        Destination { terminus, via, raw: raw.to_owned() }
+    }
+}
+
+impl Display for Destination {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(&self.terminus)
+    }
+}
+
+fn parse_destination<'de, D>(deserializer: D) -> Result<Destination, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: String = Deserialize::deserialize(deserializer)?;
+    Ok(Destination::parse(&s))
 }
 
 pub fn format_departure_time(dt: DateTime<chrono_tz::Tz>) -> String {
+    format_departure_time_tz(dt, Berlin)
+}
+
+/// Like [`format_departure_time`], but showing the clock-time fallback
+/// (for departures more than a few minutes out) in `tz` instead of always
+/// Europe/Berlin — useful for servers logging in UTC, or for users
+/// checking a connection remotely from another timezone.
+pub fn format_departure_time_tz(dt: DateTime<chrono_tz::Tz>, tz: chrono_tz::Tz) -> String {
     let minutes = dt.signed_duration_since(Local::now()).num_minutes();
     match minutes {
         0 => "now".to_owned(),
         1...9 => format!("{} min", minutes),
-        _ => format!("{}", dt.format("%H:%M")),
+        _ => format!("{}", dt.with_timezone(&tz).format("%H:%M")),
     }
 }
 
+/// Like [`format_departure_time_tz`], but prefixes the clock-time fallback
+/// with `~` when `realtime` is false, e.g. `~22:40` — a schedule-only
+/// departure is only as accurate as the static timetable, and a caller
+/// rendering it next to realtime departures may want that visible in the
+/// time itself rather than only in a separate marker.
+pub fn format_departure_time_annotated_tz(dt: DateTime<chrono_tz::Tz>, tz: chrono_tz::Tz, realtime: bool) -> String {
+    let formatted = format_departure_time_tz(dt, tz);
+    if realtime || formatted == "now" || formatted.ends_with("min") {
+        formatted
+    } else {
+        format!("~{}", formatted)
+    }
+}
+
+/// Like [`format_departure_time_annotated_tz`], but always in Europe/Berlin.
+pub fn format_departure_time_annotated(dt: DateTime<chrono_tz::Tz>, realtime: bool) -> String {
+    format_departure_time_annotated_tz(dt, Berlin, realtime)
+}
+
 /// Information about a tram station
-#[derive(Debug, Deserialize, PartialEq)]
+///
+/// `#[non_exhaustive]` so a field like a platform count or accessibility
+/// flag can be added later without breaking every downstream struct
+/// literal; construct one with [`Stop::new`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub struct Stop {
     /// human readable stop name
     pub name: String,
@@ -83,19 +266,57 @@ pub struct Stop {
     pub lon: f64,
 }
 
+impl Stop {
+    pub fn new(name: &str, id: &str, lat: f64, lon: f64) -> Stop {
+        Stop { name: name.to_owned(), id: id.to_owned(), lat, lon }
+    }
+}
+
 impl Display for Stop {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{} ({})", self.name, self.id)
     }
 }
 
+/// Coarse passenger load for a departure, from backends that report it
+/// (GTFS-RT occupancy status, or an EFA realtime extension) — not every
+/// backend, or every departure, has this. The exact wire representation
+/// isn't documented by any EFA deployment this crate has seen, so
+/// [`Departure::occupancy`] defaults to `None` rather than guessing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Occupancy {
+    Low,
+    Medium,
+    High,
+}
+
+impl Occupancy {
+    /// A single-character glyph for compact displays: ○ low, ◐ medium,
+    /// ● high.
+    pub fn glyph(&self) -> char {
+        match *self {
+            Occupancy::Low => '○',
+            Occupancy::Medium => '◐',
+            Occupancy::High => '●',
+        }
+    }
+}
+
 /// A single departure containing information about time, platform, and the train
-#[derive(Debug, Deserialize, PartialEq)]
+///
+/// `#[non_exhaustive]`: a field like platform or delay can be added later
+/// without breaking every downstream struct literal; construct one with
+/// [`Departure::new`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub struct Departure {
     /// tram line name
     pub route: String,
-    /// destination stop
-    pub destination: String,
+    /// destination stop, with any via stops split out
+    #[serde(deserialize_with = "parse_destination")]
+    pub destination: Destination,
     /// which direction the tram is going (1 or 2)
     /// does not seem to correspond to platform
     pub direction: String,
@@ -108,22 +329,81 @@ pub struct Departure {
     pub realtime: bool,
     /// not sure. seen 0 or 2 as values
     pub traction: u32,
+    /// platform/track this departure leaves from, if the backend reports
+    /// one — not every EFA deployment (or every stop) does, so this is
+    /// `None` rather than an empty string when absent.
+    #[serde(default)]
+    pub platform: Option<String>,
+    /// how full this vehicle is, if the backend reports it. See [`Occupancy`].
+    #[serde(default)]
+    pub occupancy: Option<Occupancy>,
+}
+
+impl Departure {
+    /// One argument per field mirrors this crate's other constructors
+    /// (e.g. [`EfaPreset::new`](preset::EfaPreset::new)). `platform` and
+    /// `occupancy` default to `None` here rather than being arguments —
+    /// see [`with_platform`](Departure::with_platform) and
+    /// [`with_occupancy`](Departure::with_occupancy) — so that adding the
+    /// *next* field this crate learns to report doesn't, once again,
+    /// change every existing caller's argument list; `#[non_exhaustive]`
+    /// already stops them from using a struct literal instead.
+    pub fn new(route: &str, destination: Destination, direction: &str, time: DateTime<chrono_tz::Tz>, lowfloor: bool, realtime: bool, traction: u32) -> Departure {
+        Departure {
+            route: route.to_owned(),
+            destination,
+            direction: direction.to_owned(),
+            time,
+            lowfloor,
+            realtime,
+            traction,
+            platform: None,
+            occupancy: None,
+        }
+    }
+
+    /// Set the platform/track this departure leaves from — see
+    /// [`Departure::platform`].
+    pub fn with_platform(mut self, platform: &str) -> Departure {
+        self.platform = Some(platform.to_owned());
+        self
+    }
+
+    /// Set how full this vehicle is — see [`Departure::occupancy`].
+    pub fn with_occupancy(mut self, occupancy: Occupancy) -> Departure {
+        self.occupancy = Some(occupancy);
+        self
+    }
+
+    /// Whether this departure has no live tracking, so [`Departure::time`]
+    /// is only as accurate as the static timetable.
+    pub fn is_schedule_only(&self) -> bool {
+        !self.realtime
+    }
 }
 
 impl Display for Departure {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let rt = if self.realtime {"*"} else {" "};
-        write!(f, "{:<3} {:<20} {}{}", self.route, self.destination, format_departure_time(self.time), rt)
+        let wheelchair = if self.lowfloor {"\u{267f}"} else {" "};
+        let platform = self.platform.as_ref().map(|p| format!(" Pl.{}", p)).unwrap_or_default();
+        let occupancy = self.occupancy.map(|o| format!(" {}", o.glyph())).unwrap_or_default();
+        write!(f, "{:<3} {:<20} {}{} {}{}{}", self.route, self.destination, format_departure_time(self.time), rt, wheelchair, platform, occupancy)
     }
 }
 
 #[derive(Debug, Deserialize, PartialEq)]
-struct SearchAnswer {
-    stops: Vec<Stop>,
+pub(crate) struct SearchAnswer {
+    pub(crate) stops: Vec<Stop>,
 }
 
 /// Answer to a query for departures. Contains stop name, timestamp, and all departures.
-#[derive(Debug, Deserialize, PartialEq)]
+///
+/// `#[non_exhaustive]`: construct one with [`Departures::new`] so a later
+/// field (e.g. a disruption notice) doesn't break downstream struct
+/// literals.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
 pub struct Departures {
     /// response timestamp
     #[serde(deserialize_with = "parse_timestamp")]
@@ -135,12 +415,211 @@ pub struct Departures {
     pub departures: Vec<Departure>,
 }
 
+impl Departures {
+    pub fn new(timestamp: DateTime<chrono_tz::Tz>, stop_name: &str, departures: Vec<Departure>) -> Departures {
+        Departures { timestamp, stop_name: stop_name.to_owned(), departures }
+    }
+
+    /// Parse a board from a raw [`serde_json::Value`], e.g. one fetched
+    /// via [`KvvClient::get_raw`](client::KvvClient::get_raw) — for
+    /// callers who inspected a field this type doesn't cover yet and now
+    /// want the typed board back without a second round trip.
+    pub fn from_value(value: serde_json::Value) -> Result<Departures, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    /// Keep only departures served by a low-floor (wheelchair-accessible)
+    /// vehicle.
+    ///
+    /// Note that this only reflects `Departure::lowfloor` as reported by the
+    /// live endpoint; stop-level accessibility (step-free platform access
+    /// etc.) is not covered here since the live API does not expose it.
+    pub fn accessible_only(&self) -> Vec<&Departure> {
+        self.departures.iter().filter(|d| d.lowfloor).collect()
+    }
+
+    /// A natural-language summary of this board, one sentence per line,
+    /// suitable for voice assistants and TTS. See
+    /// [`speech::summarize`](speech::summarize).
+    pub fn summarize(&self, lang: locale::Lang) -> String {
+        speech::summarize(self, lang)
+    }
+
+    /// How long ago this board's response timestamp was, compared to now,
+    /// corrected for any observed [`clockskew`] between this machine and
+    /// the server.
+    pub fn age(&self) -> chrono::Duration {
+        let now = clockskew::correct(Local::now().with_timezone(&self.timestamp.timezone()));
+        now.signed_duration_since(self.timestamp)
+    }
+
+    /// Whether this board is older than `max_age` and should be flagged as
+    /// stale rather than shown silently, e.g. on a public display.
+    pub fn is_stale(&self, max_age: chrono::Duration) -> bool {
+        self.age() > max_age
+    }
+
+    /// Fraction of departures on this board with live tracking, from
+    /// `0.0` (none) to `1.0` (all) — a quick data-quality signal for a
+    /// caller deciding whether to trust the times shown. `1.0` for an
+    /// empty board, since there are no schedule-only departures to
+    /// distrust.
+    pub fn realtime_ratio(&self) -> f64 {
+        if self.departures.is_empty() {
+            return 1.0;
+        }
+        let realtime = self.departures.iter().filter(|d| d.realtime).count();
+        realtime as f64 / self.departures.len() as f64
+    }
+
+    /// The soonest departure on this board, if there are any.
+    pub fn earliest(&self) -> Option<&Departure> {
+        self.departures.iter().min_by_key(|d| d.time)
+    }
+
+    /// The soonest departure served by `route`, if any.
+    pub fn next_for_route(&self, route: &str) -> Option<&Departure> {
+        self.departures.iter().filter(|d| d.route == route).min_by_key(|d| d.time)
+    }
+
+    /// The soonest departure whose terminus is `destination`, if any.
+    pub fn next_towards(&self, destination: &str) -> Option<&Departure> {
+        self.departures.iter().filter(|d| d.destination.terminus == destination).min_by_key(|d| d.time)
+    }
+
+    /// All departures due within `duration` from now, earliest first.
+    pub fn within_next(&self, duration: Duration) -> Vec<&Departure> {
+        let cutoff = Local::now() + duration;
+        let mut departures: Vec<&Departure> = self.departures.iter().filter(|d| d.time.with_timezone(&Local) <= cutoff).collect();
+        departures.sort_by_key(|d| d.time);
+        departures
+    }
+
+    /// The last departure on this board served by a regular (non-night)
+    /// line, if any — i.e. the last departure before the gap where only
+    /// KVV's `NL`-prefixed night lines (or nothing, outside a Friday or
+    /// Saturday night) are still running. Useful for flagging "last tram
+    /// tonight" on a board, per [`schedule::is_night_service`].
+    pub fn last_before_night_service(&self) -> Option<&Departure> {
+        self.departures.iter().filter(|d| !linemeta::Line::new(d.route.clone()).is_night_line()).max_by_key(|d| d.time)
+    }
+
+    /// Number of departures on this board.
+    pub fn len(&self) -> usize {
+        self.departures.len()
+    }
+
+    /// Whether this board has no departures at all.
+    pub fn is_empty(&self) -> bool {
+        self.departures.is_empty()
+    }
+
+    /// An iterator over the departures on this board, in whatever order
+    /// the API returned them.
+    pub fn iter(&self) -> std::slice::Iter<'_, Departure> {
+        self.departures.iter()
+    }
+}
+
+/// Lets a [`Departures`] board be used anywhere a `&[Departure]` is
+/// expected, e.g. sorting or slicing, without going through the
+/// `departures` field explicitly.
+impl Deref for Departures {
+    type Target = [Departure];
+
+    fn deref(&self) -> &[Departure] {
+        &self.departures
+    }
+}
+
+impl IntoIterator for Departures {
+    type Item = Departure;
+    type IntoIter = std::vec::IntoIter<Departure>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.departures.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Departures {
+    type Item = &'a Departure;
+    type IntoIter = std::slice::Iter<'a, Departure>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.departures.iter()
+    }
+}
+
+/// Default request timeout, used unless [`set_timeout`] overrides it.
+const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+static TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_TIMEOUT_SECS);
+static RETRIES: AtomicU32 = AtomicU32::new(0);
+static GZIP: AtomicBool = AtomicBool::new(true);
+
+/// Override the request timeout used by every subsequent call in this
+/// process. Scripts on flaky connections may want this shorter or longer
+/// than the default of 30 seconds.
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT_SECS.store(timeout.num_seconds().max(1) as u64, Ordering::Relaxed);
+}
+
+/// Override how many times a failed request is retried before giving up.
+/// Defaults to 0 (no retries).
+pub fn set_retries(retries: u32) {
+    RETRIES.store(retries, Ordering::Relaxed);
+}
+
+/// Enable or disable gzip/deflate response compression for every
+/// subsequent call in this process (on by default). Boards polling over
+/// a metered connection want this left on; disable it only to rule out a
+/// decompression bug while debugging.
+pub fn set_gzip(enable: bool) {
+    GZIP.store(enable, Ordering::Relaxed);
+}
+
+fn client() -> Client {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(TIMEOUT_SECS.load(Ordering::Relaxed)))
+        .gzip(GZIP.load(Ordering::Relaxed))
+        .build()
+        .expect("failed to build HTTP client")
+}
+
 fn query<T: DeserializeOwned>(path: &str, params: Vec<(&str, &str)>) -> Result<T, reqwest::Error> {
+    let active_preset = preset::active();
     let mut params = params.clone();
-    params.push(("key", API_KEY));
-
-    let url = Url::parse_with_params(&format!("{}{}", API_BASE, path), params).unwrap();
-    Client::new().get(url).send()?.error_for_status()?.json()
+    params.push(("key", &active_preset.key));
+
+    let url = Url::parse_with_params(&format!("{}{}", active_preset.base_url, path), params).unwrap();
+
+    let mut attempts_left = RETRIES.load(Ordering::Relaxed) + 1;
+    let mut attempt = 0u32;
+    loop {
+        attempt += 1;
+        let started = std::time::Instant::now();
+        let result = client().get(url.clone()).send().and_then(|r| r.error_for_status()).and_then(|mut r| r.json());
+        let latency_ms = started.elapsed().as_millis() as u64;
+        attempts_left -= 1;
+        match result {
+            Ok(value) => {
+                metrics::record_request(path, latency_ms, true);
+                return Ok(value);
+            }
+            Err(e) => {
+                metrics::record_request(path, latency_ms, false);
+                error_hook::report(error_hook::ErrorContext {
+                    endpoint: path.to_owned(),
+                    attempt,
+                    status: e.status().map(|s| s.as_u16()),
+                    body_snippet: None,
+                });
+                if attempts_left == 0 {
+                    return Err(e);
+                }
+            }
+        }
+    }
 }
 
 fn search(path: &str) -> Result<Vec<Stop>, reqwest::Error> {
@@ -152,11 +631,64 @@ pub fn search_by_name(name: &str) -> Result<Vec<Stop>, reqwest::Error> {
     search(&format!("stops/byname/{}", name))
 }
 
+/// Search stops by their name, re-ranked client-side by similarity to the
+/// query since the API's own ordering is often unhelpful.
+pub fn search_by_name_ranked(name: &str) -> Result<Vec<ranking::RankedStop>, reqwest::Error> {
+    search_by_name(name).map(|stops| ranking::rank_by_name(name, stops))
+}
+
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Search stops by name, firing off a few plausible query variants (the raw
+/// query, whitespace-normalized, and prefixed with "Karlsruhe ") concurrently
+/// and merging the deduplicated results, to cope with sloppy user input
+/// without paying for serial round trips.
+pub fn search_by_name_multi(name: &str) -> Result<Vec<Stop>, reqwest::Error> {
+    let mut variants = vec![name.to_owned(), normalize_name(name), format!("Karlsruhe {}", name)];
+    variants.dedup();
+
+    let handles: Vec<_> = variants
+        .into_iter()
+        .map(|v| std::thread::spawn(move || search_by_name(&v)))
+        .collect();
+
+    let mut merged: Vec<Stop> = Vec::new();
+    let mut last_err = None;
+    for handle in handles {
+        match handle.join().expect("search thread panicked") {
+            Ok(stops) => {
+                for stop in stops {
+                    if !merged.iter().any(|s: &Stop| s.id == stop.id) {
+                        merged.push(stop);
+                    }
+                }
+            }
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    match last_err {
+        Some(e) if merged.is_empty() => Err(e),
+        _ => Ok(merged),
+    }
+}
+
 /// Search stops in the vicinity of a position given as latitude and longitude
 pub fn search_by_latlon(lat: f64, lon: f64) -> Result<Vec<Stop>, reqwest::Error> {
     search(&format!("stops/bylatlon/{}/{}", lat, lon))
 }
 
+/// Search stops near a position and fetch departures for each of them in
+/// one call, for "what leaves near me" style features.
+pub fn departures_by_latlon(lat: f64, lon: f64) -> Result<Vec<(Stop, Departures)>, reqwest::Error> {
+    search_by_latlon(lat, lon)?
+        .into_iter()
+        .map(|stop| departures_by_stop(&stop.id).map(|deps| (stop, deps)))
+        .collect()
+}
+
 /// Get a stop by its id. Returns None if the given stop id does not exist.
 pub fn search_by_stop_id(stop_id: &str) -> Result<Option<Stop>, reqwest::Error> {
     match query(&format!("stops/bystop/{}", stop_id), vec![]) {
@@ -171,11 +703,15 @@ pub fn search_by_stop_id(stop_id: &str) -> Result<Option<Stop>, reqwest::Error>
 }
 
 fn departures(path: &str) -> Result<Departures, reqwest::Error> {
-    query::<Departures>(path, vec![])
+    let deps = query::<Departures>(path, vec![])?;
+    clockskew::observe(deps.timestamp);
+    Ok(deps)
 }
 
 fn departures_with_max(path: &str, max_info: u32) -> Result<Departures, reqwest::Error> {
-    query::<Departures>(path, vec![("maxInfos", &max_info.to_string())])
+    let deps = query::<Departures>(path, vec![("maxInfos", &max_info.to_string())])?;
+    clockskew::observe(deps.timestamp);
+    Ok(deps)
 }
 
 /// Get next departures for a stop up to a maximum of max_info entries (may be less)
@@ -204,6 +740,98 @@ pub fn departures_by_route(stop_id: &str, route: &str) -> Result<Departures, req
     departures(&format!("departures/byroute/{}/{}", route, stop_id))
 }
 
+/// Like [`departures_by_stop`], but parsed leniently via [`raw::RawDepartures`]:
+/// a departure with a malformed field (e.g. an unrecognized time format)
+/// is dropped, with a [`raw::ParseIssue`] explaining why, instead of failing
+/// the whole board the way `departures_by_stop` does.
+pub fn departures_by_stop_lenient(stop_id: &str) -> Result<(Departures, Vec<raw::ParseIssue>), reqwest::Error> {
+    let raw: raw::RawDepartures = query(&format!("departures/bystop/{}", stop_id), vec![])?;
+    let (deps, issues) = raw.into_domain();
+    clockskew::observe(deps.timestamp);
+    Ok((deps, issues))
+}
+
+/// Like [`departures_by_route`], but parsed leniently via [`raw::RawDepartures`]:
+/// a departure with a malformed field (e.g. an unrecognized time format)
+/// is dropped, with a [`raw::ParseIssue`] explaining why, instead of failing
+/// the whole board the way `departures_by_route` does.
+pub fn departures_by_route_lenient(stop_id: &str, route: &str) -> Result<(Departures, Vec<raw::ParseIssue>), reqwest::Error> {
+    let raw: raw::RawDepartures = query(&format!("departures/byroute/{}/{}", route, stop_id), vec![])?;
+    let (deps, issues) = raw.into_domain();
+    clockskew::observe(deps.timestamp);
+    Ok((deps, issues))
+}
+
+/// If a name search has no exact match and still yields more candidates than
+/// this, we refuse to guess and report the ambiguity instead.
+const AMBIGUOUS_THRESHOLD: usize = 3;
+
+/// Error returned by [`departures_by_name`].
+#[derive(Debug)]
+pub enum NameLookupError {
+    /// the underlying HTTP/JSON request failed
+    Request(reqwest::Error),
+    /// no stop matched the given name
+    NotFound,
+    /// more than one stop could plausibly be meant, and none of them is an
+    /// exact name match to break the tie
+    Ambiguous(Vec<Stop>),
+}
+
+impl From<reqwest::Error> for NameLookupError {
+    fn from(e: reqwest::Error) -> Self {
+        NameLookupError::Request(e)
+    }
+}
+
+impl Display for NameLookupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            NameLookupError::Request(ref e) => write!(f, "{}", e),
+            NameLookupError::NotFound => write!(f, "no stop found for that name"),
+            NameLookupError::Ambiguous(ref stops) => {
+                write!(f, "name is ambiguous between {} stops", stops.len())
+            }
+        }
+    }
+}
+
+/// How close a runner-up's [`ranking::RankedStop::score`] must be to the
+/// best match's to still count as a plausible contender, rather than a
+/// clearly worse match that just happens to also be in the results.
+const AMBIGUOUS_SCORE_MARGIN: f64 = 0.05;
+
+/// Pick the best-ranked stop out of a [`ranking::rank_by_name`] result, or
+/// report why none could be picked outright.
+///
+/// The top-scored stop always wins outright if it's an exact
+/// (case-insensitive) name match. Otherwise, if more than a handful of
+/// candidates are within [`AMBIGUOUS_SCORE_MARGIN`] of the best score,
+/// [`NameLookupError::Ambiguous`] is returned instead of guessing among them.
+fn best_ranked_stop(ranked: Vec<ranking::RankedStop>) -> Result<Stop, NameLookupError> {
+    if ranked.is_empty() {
+        return Err(NameLookupError::NotFound);
+    }
+
+    let best_score = ranked[0].score;
+    let contenders = ranked.iter().filter(|r| r.score >= best_score - AMBIGUOUS_SCORE_MARGIN).count();
+
+    if best_score < 1.0 && contenders > AMBIGUOUS_THRESHOLD {
+        return Err(NameLookupError::Ambiguous(ranked.into_iter().map(|r| r.stop).collect()));
+    }
+
+    Ok(ranked.into_iter().next().unwrap().stop)
+}
+
+/// Search for a stop by name and return its departures in one call.
+///
+/// Candidates are re-ranked by [`ranking::rank_by_name`] and the best match
+/// picked by [`best_ranked_stop`].
+pub fn departures_by_name(name: &str) -> Result<Departures, NameLookupError> {
+    let stop = best_ranked_stop(search_by_name_ranked(name)?)?;
+    departures_by_stop(&stop.id).map_err(NameLookupError::from)
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -224,4 +852,72 @@ mod tests {
         let stops: SearchAnswer = serde_json::from_str(EXAMPLE_STOPS).unwrap();
         assert_eq!(stops, stops_ref);
     }
+
+    fn stop(name: &str, id: &str) -> Stop {
+        Stop { name: name.to_owned(), id: id.to_owned(), lat: 0.0, lon: 0.0 }
+    }
+
+    #[test]
+    fn best_ranked_stop_picks_exact_match_even_among_many_candidates() {
+        let ranked = ranking::rank_by_name(
+            "Karlsruhe Marktplatz",
+            vec![
+                stop("Karlsruhe Marktplatz", "exact"),
+                stop("Karlsruhe Hauptfriedhof", "a"),
+                stop("Karlsruhe Hauptbahnhof", "b"),
+                stop("Karlsruhe Durlach", "c"),
+                stop("Karlsruhe Rheinhafen", "d"),
+            ],
+        );
+        let chosen = best_ranked_stop(ranked).unwrap();
+        assert_eq!(chosen.id, "exact");
+    }
+
+    #[test]
+    fn best_ranked_stop_picks_the_clear_best_match() {
+        let ranked = ranking::rank_by_name("Marktplatz", vec![stop("Karlsruhe Marktplatz", "a"), stop("Rastatt Bahnhof", "b")]);
+        let chosen = best_ranked_stop(ranked).unwrap();
+        assert_eq!(chosen.id, "a");
+    }
+
+    #[test]
+    fn best_ranked_stop_is_ambiguous_with_many_close_scores_and_no_exact_match() {
+        // Each candidate is a single-character substitution away from the
+        // query, at a different position, so all four tie on score exactly.
+        let ranked = ranking::rank_by_name(
+            "marktplatz",
+            vec![stop("narktplatz", "a"), stop("mbrktplatz", "b"), stop("maaktplatz", "c"), stop("markaplatz", "d")],
+        );
+        match best_ranked_stop(ranked) {
+            Err(NameLookupError::Ambiguous(stops)) => assert_eq!(stops.len(), 4),
+            other => panic!("expected Ambiguous, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn best_ranked_stop_is_not_found_with_no_candidates() {
+        assert!(matches!(best_ranked_stop(vec![]), Err(NameLookupError::NotFound)));
+    }
+
+    #[test]
+    fn destination_parse_without_via_has_no_via_stops() {
+        let dest = Destination::parse("Rheinstetten");
+        assert_eq!(dest.terminus, "Rheinstetten");
+        assert_eq!(dest.via, Vec::<String>::new());
+        assert_eq!(dest.raw, "Rheinstetten");
+    }
+
+    #[test]
+    fn destination_parse_splits_single_via_stop() {
+        let dest = Destination::parse("Durlach über Tullastraße");
+        assert_eq!(dest.terminus, "Durlach");
+        assert_eq!(dest.via, vec!["Tullastraße".to_owned()]);
+    }
+
+    #[test]
+    fn destination_parse_splits_multiple_comma_separated_via_stops() {
+        let dest = Destination::parse("Durlach über Tullastraße, Durlach Bahnhof");
+        assert_eq!(dest.terminus, "Durlach");
+        assert_eq!(dest.via, vec!["Tullastraße".to_owned(), "Durlach Bahnhof".to_owned()]);
+    }
 }
@@ -0,0 +1,62 @@
+//! Static metadata for KVV lines (official colors, full names, typical
+//! termini), so consumers don't each have to maintain their own mapping
+//! just to render a properly colored line badge.
+//!
+//! The live API only ever returns the short route code (e.g. `"S2"`); this
+//! table is maintained by hand against KVV's published line network and is
+//! not guaranteed to be exhaustive or to track renumbering immediately.
+
+/// Static metadata about a KVV line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetadata {
+    /// the short route code as returned by the API, e.g. `"S2"`
+    pub route: &'static str,
+    /// official line color as a `#rrggbb` hex string
+    pub color: &'static str,
+    /// full marketing name of the line, if it has one
+    pub name: &'static str,
+    /// the line's usual termini
+    pub termini: &'static [&'static str],
+}
+
+const LINES: &[LineMetadata] = &[
+    LineMetadata { route: "S1", color: "#dd6ba3", name: "S1", termini: &["Hochstetten", "Bad Herrenalb / Ittersbach"] },
+    LineMetadata { route: "S11", color: "#dd6ba3", name: "S11", termini: &["Hochstetten", "Pforzheim Hbf"] },
+    LineMetadata { route: "S2", color: "#f39200", name: "S2", termini: &["Spöck", "Rheinstetten"] },
+    LineMetadata { route: "S4", color: "#00a651", name: "S4", termini: &["Karlsruhe-Durlach", "Bretten/Eppingen"] },
+    LineMetadata { route: "S5", color: "#8dc63f", name: "S5", termini: &["Heilbronn", "Karlsruhe Tullastraße"] },
+    LineMetadata { route: "S7", color: "#662483", name: "S7", termini: &["Karlsruhe Albtalbahnhof", "Achern"] },
+    LineMetadata { route: "S8", color: "#009ddc", name: "S8", termini: &["Karlsruhe Hbf", "Pforzheim Hbf"] },
+    LineMetadata { route: "1", color: "#e2001a", name: "Linie 1", termini: &["Rheinstrandsiedlung", "Durlach Turmberg"] },
+    LineMetadata { route: "2", color: "#f39200", name: "Linie 2", termini: &["Rintheim", "Rüppurr"] },
+    LineMetadata { route: "3", color: "#662483", name: "Linie 3", termini: &["Durlach", "Mühlburg"] },
+    LineMetadata { route: "4", color: "#009ddc", name: "Linie 4", termini: &["Waldstadt", "Rheinhafen"] },
+    LineMetadata { route: "5", color: "#8dc63f", name: "Linie 5", termini: &["Rintheim", "Oberreut"] },
+    LineMetadata { route: "6", color: "#00a651", name: "Linie 6", termini: &["Neureut", "Rheinstetten"] },
+];
+
+/// A KVV line, identified by its short route code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line(pub String);
+
+impl Line {
+    /// Create a handle for the given route code (as returned in
+    /// [`Departure::route`](::Departure::route)).
+    pub fn new<S: Into<String>>(route: S) -> Line {
+        Line(route.into())
+    }
+
+    /// Look up the static metadata for this line, if known.
+    pub fn metadata(&self) -> Option<&'static LineMetadata> {
+        LINES.iter().find(|l| l.route == self.0)
+    }
+
+    /// Whether this is one of KVV's night lines (route codes prefixed
+    /// `NL`, e.g. `"NL2"`), which only run the reduced late-night/weekend
+    /// network instead of the regular daytime one. Not in [`LINES`] above,
+    /// since night lines aren't part of the scheduled network this crate
+    /// tracks colors and termini for.
+    pub fn is_night_line(&self) -> bool {
+        self.0.starts_with("NL")
+    }
+}
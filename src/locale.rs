@@ -0,0 +1,41 @@
+//! Output language selection for the CLI.
+//!
+//! The crate's own formatting stays English; this only affects the small
+//! set of user-facing strings that the CLI assembles itself, since the
+//! target audience for public displays is largely German-speaking.
+
+use std::env;
+
+/// Output language for CLI strings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lang {
+    English,
+    German,
+}
+
+impl Lang {
+    /// Detect the preferred language from the `LANG` environment variable,
+    /// falling back to English.
+    pub fn detect() -> Lang {
+        match env::var("LANG") {
+            Ok(ref val) if val.starts_with("de") => Lang::German,
+            _ => Lang::English,
+        }
+    }
+
+    /// Parse a `--lang` CLI argument value (`"de"` or `"en"`).
+    pub fn parse(s: &str) -> Option<Lang> {
+        match s {
+            "de" => Some(Lang::German),
+            "en" => Some(Lang::English),
+            _ => None,
+        }
+    }
+
+    pub fn not_found_stop(self, query: &str) -> String {
+        match self {
+            Lang::English => format!("Could not find any stop matching \"{}\"", query),
+            Lang::German => format!("Keine Haltestelle gefunden, die zu \"{}\" passt", query),
+        }
+    }
+}
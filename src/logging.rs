@@ -0,0 +1,38 @@
+//! Event logging for long-running modes (daemon, server), in either
+//! free-form text or structured JSON (one event per line) for ingestion
+//! into journald/Loki.
+
+use chrono::Local;
+use serde_json::json;
+
+/// Output format for [`Logger`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+/// Logs discrete events (a poll, a notification, an error) with a
+/// timestamp, the stop involved, and the outcome.
+pub struct Logger {
+    format: LogFormat,
+}
+
+impl Logger {
+    pub fn new(format: LogFormat) -> Logger {
+        Logger { format }
+    }
+
+    /// Log one event. `event` names what happened (e.g. `"poll"`), `stop_id`
+    /// is the stop it concerned, and `outcome` is a short result
+    /// (`"ok"`, `"timeout"`, ...).
+    pub fn event(&self, event: &str, stop_id: &str, outcome: &str) {
+        let timestamp = Local::now().to_rfc3339();
+        match self.format {
+            LogFormat::Json => {
+                println!("{}", json!({"timestamp": timestamp, "event": event, "stop_id": stop_id, "outcome": outcome}));
+            }
+            LogFormat::Text => println!("{} {} stop={} outcome={}", timestamp, event, stop_id, outcome),
+        }
+    }
+}
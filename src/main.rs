@@ -1,12 +1,277 @@
 extern crate kvvliveapi;
 extern crate reqwest;
+extern crate chrono;
+extern crate chrono_tz;
+extern crate ctrlc;
+extern crate enable_ansi_support;
 
+use chrono::Local;
 use kvvliveapi::*;
+use kvvliveapi::abbreviate::Abbreviator;
+use kvvliveapi::speech::plain_speech_lang;
+use kvvliveapi::locale::Lang;
+use kvvliveapi::cache;
+use kvvliveapi::config::Config;
+use kvvliveapi::recorder::{self, Recorder};
+use kvvliveapi::stats;
+use kvvliveapi::diff::{self, Change};
+use kvvliveapi::doctor;
+use kvvliveapi::fare::{self, TicketType, ZoneMap};
+use kvvliveapi::map;
+use kvvliveapi::leave;
+use kvvliveapi::smoothing::Smoother;
+use kvvliveapi::transfer::{self, TransferPlan, TransferStatus};
+use kvvliveapi::nlquery;
+use kvvliveapi::qr;
+use kvvliveapi::staticmap;
+use kvvliveapi::svg;
+use kvvliveapi::paths;
+use kvvliveapi::rpc;
+#[cfg(feature = "server")]
+use kvvliveapi::server;
 
 use std::env::args;
 use std::error::Error;
+use std::thread;
+use std::time::Duration as StdDuration;
+
+/// Fetch a board, falling back to the last cached one for `cache_key` if
+/// the request fails and `no_stale` isn't set.
+fn fetch_with_fallback<F>(cache_key: &str, no_stale: bool, fetch: F) -> Result<Departures, reqwest::Error>
+where
+    F: FnOnce() -> Result<Departures, reqwest::Error>,
+{
+    match fetch() {
+        Ok(deps) => {
+            let _ = cache::store(cache_key, &deps);
+            if let Ok(rec) = Recorder::open(recorder::default_db_path()) {
+                let _ = rec.record(cache_key, &deps);
+            }
+            Ok(deps)
+        }
+        Err(e) => {
+            if !no_stale {
+                if let Ok(deps) = cache::load(cache_key) {
+                    eprintln!("data from {} (offline)", deps.timestamp.format("%H:%M"));
+                    return Ok(deps);
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+fn since_from_arg(s: &str) -> chrono::DateTime<chrono_tz::Tz> {
+    use chrono::{Duration, TimeZone};
+    let now = chrono::Local::now().with_timezone(&chrono_tz::Europe::Berlin);
+    let midnight_today = now.date_naive().and_hms_opt(0, 0, 0).unwrap();
+    let midnight_today = chrono_tz::Europe::Berlin.from_local_datetime(&midnight_today).unwrap();
+    match s {
+        "yesterday" => midnight_today - Duration::days(1),
+        _ => midnight_today,
+    }
+}
+
+fn print_departure(dep: &Departure, abbr: &Abbreviator, plain_speech_format: bool, lang: Lang, tz: chrono_tz::Tz) {
+    if plain_speech_format {
+        println!("{}", plain_speech_lang(dep, lang));
+        return;
+    }
+    let rt = if dep.realtime {"*"} else {" "};
+    let wheelchair = if dep.lowfloor {"\u{267f}"} else {" "};
+    let dest = abbr.apply(&dep.destination.terminus);
+    let platform = dep.platform.as_ref().map(|p| format!(" Pl.{}", p)).unwrap_or_default();
+    let occupancy = dep.occupancy.map(|o| format!(" {}", o.glyph())).unwrap_or_default();
+    println!("{:<3} {:<20} {}{} {}{}{}", dep.route, dest, format_departure_time_tz(dep.time, tz), rt, wheelchair, platform, occupancy);
+}
+
+/// Print a warning to stderr if `deps` is older than `max_age` — silent
+/// stale data on a public display is worse than no data.
+fn warn_if_stale(deps: &Departures, max_age: chrono::Duration) {
+    if deps.is_stale(max_age) {
+        eprintln!("warning: data is {} minutes old", deps.age().num_minutes());
+    }
+}
+
+fn print_departures<'a, I: IntoIterator<Item = &'a Departure>>(deps: I, abbr: &Abbreviator, plain_speech_format: bool, lang: Lang, tz: chrono_tz::Tz) {
+    for dep in deps {
+        print_departure(dep, abbr, plain_speech_format, lang, tz);
+    }
+}
+
+/// Print a single row of a watched board, colored by how it changed since
+/// the previous poll so the eye catches updates on a wall display.
+fn print_watched_departure(change: &diff::DepartureChange, abbr: &Abbreviator, plain_speech_format: bool, lang: Lang, tz: chrono_tz::Tz) {
+    let (prefix, suffix) = match change.change {
+        Change::New => ("\x1B[32m", "\x1B[0m"),      // green
+        Change::Earlier(_) => ("\x1B[33m", "\x1B[0m"), // yellow
+        Change::Later(_) => ("\x1B[36m", "\x1B[0m"),   // cyan
+        Change::Gone => ("\x1B[2m", "\x1B[0m"),        // dim
+        Change::Unchanged => ("", ""),
+    };
+    print!("{}", prefix);
+    print_departure(&change.departure, abbr, plain_speech_format, lang, tz);
+    print!("{}", suffix);
+}
+
+/// Poll `fetch` every `interval` seconds, redrawing the board and
+/// highlighting rows that changed since the previous refresh. Runs until
+/// killed.
+fn watch<F>(interval: u64, abbr: &Abbreviator, plain_speech_format: bool, lang: Lang, tz: chrono_tz::Tz, smooth: bool, max_age: chrono::Duration, mut fetch: F) -> Result<(), reqwest::Error>
+where
+    F: FnMut() -> Result<Departures, reqwest::Error>,
+{
+    // plain SIGINT/Ctrl-C would otherwise leave the Windows console in
+    // whatever state the last redraw left it in
+    let _ = ctrlc::set_handler(|| {
+        println!("\x1B[0m");
+        std::process::exit(0);
+    });
+
+    let mut previous: Option<Departures> = None;
+    let mut just_shown_gone: Vec<Departure> = Vec::new();
+    let mut smoother = Smoother::new();
+    loop {
+        let mut deps = fetch()?;
+        warn_if_stale(&deps, max_age);
+        if smooth {
+            deps = smoother.smooth(&deps);
+        }
+        print!("\x1B[2J\x1B[H");
+        println!("{}", deps.stop_name);
+
+        match previous {
+            Some(ref prev) => {
+                let changes = diff::diff_boards(prev, &deps);
+                let to_show: Vec<&diff::DepartureChange> = changes
+                    .iter()
+                    .filter(|c| c.change != Change::Gone || !just_shown_gone.contains(&c.departure))
+                    .collect();
+                just_shown_gone = to_show
+                    .iter()
+                    .filter(|c| c.change == Change::Gone)
+                    .map(|c| c.departure.clone())
+                    .collect();
+                for change in to_show {
+                    print_watched_departure(change, abbr, plain_speech_format, lang, tz);
+                }
+            }
+            None => print_departures(&deps.departures, abbr, plain_speech_format, lang, tz),
+        }
+
+        previous = Some(deps);
+        thread::sleep(StdDuration::from_secs(interval));
+    }
+}
+
+fn do_stuff(mut args: Vec<String>) -> Result<(), reqwest::Error> {
+    let accessible_only = args.iter().any(|a| a == "--accessible");
+    args.retain(|a| a != "--accessible");
+
+    let plain_speech_format = args.iter().any(|a| a == "plain-speech");
+    let svg_format = args.iter().any(|a| a == "svg");
+    if let Some(pos) = args.iter().position(|a| a == "--format") {
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+
+    let mut lang = Lang::detect();
+    if let Some(pos) = args.iter().position(|a| a == "--lang") {
+        if let Some(l) = args.get(pos + 1).and_then(|s| Lang::parse(s)) {
+            lang = l;
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+
+    if let Some(pos) = args.iter().position(|a| a == "--timeout") {
+        if let Some(secs) = args.get(pos + 1).and_then(|s| s.parse::<i64>().ok()) {
+            set_timeout(chrono::Duration::seconds(secs));
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+    if let Some(pos) = args.iter().position(|a| a == "--retries") {
+        if let Some(retries) = args.get(pos + 1).and_then(|s| s.parse::<u32>().ok()) {
+            set_retries(retries);
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+    if args.iter().any(|a| a == "--no-gzip") {
+        set_gzip(false);
+    }
+    args.retain(|a| a != "--no-gzip");
+
+    let no_stale = args.iter().any(|a| a == "--no-stale");
+    args.retain(|a| a != "--no-stale");
+
+    let smooth = args.iter().any(|a| a == "--smooth");
+    args.retain(|a| a != "--smooth");
+
+    let mut tz = chrono_tz::Europe::Berlin;
+    if let Some(pos) = args.iter().position(|a| a == "--tz") {
+        if let Some(parsed) = args.get(pos + 1).and_then(|s| s.parse::<chrono_tz::Tz>().ok()) {
+            tz = parsed;
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+
+    let mut max_age = chrono::Duration::seconds(120);
+    if let Some(pos) = args.iter().position(|a| a == "--max-age") {
+        if let Some(secs) = args.get(pos + 1).and_then(|s| s.parse::<i64>().ok()) {
+            max_age = chrono::Duration::seconds(secs);
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+
+    let watch_interval = args.iter().position(|a| a == "--watch").map(|pos| {
+        match args.get(pos + 1).and_then(|s| s.parse::<u64>().ok()) {
+            Some(interval) => {
+                args.drain(pos..(pos + 2).min(args.len()));
+                interval
+            }
+            None => {
+                args.remove(pos);
+                20
+            }
+        }
+    });
+
+    let mut abbr = Abbreviator::default();
+    if let Some(pos) = args.iter().position(|a| a == "--max-dest-len") {
+        if let Some(len) = args.get(pos + 1).and_then(|s| s.parse::<usize>().ok()) {
+            abbr = abbr.max_len(len);
+        }
+        args.drain(pos..(pos + 2).min(args.len()));
+    }
+
+    if args.get(1).map(String::as_str) == Some("rpc") {
+        rpc::run().unwrap_or_else(|e| error(&e.to_string()));
+        return Ok(());
+    }
+
+    if args.get(1).map(String::as_str) == Some("doctor") {
+        let mut all_ok = true;
+        for check in doctor::run(args.get(2).map(String::as_str)) {
+            all_ok &= check.ok;
+            println!("{} {:<18} {}", if check.ok { "[ok]  " } else { "[FAIL]" }, check.name, check.detail);
+        }
+        if !all_ok {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if args.get(1).map(String::as_str) == Some("server") {
+        #[cfg(feature = "server")]
+        let result = {
+            let port = args.iter().position(|a| a == "--port").and_then(|pos| args.get(pos + 1)).and_then(|p| p.parse().ok()).unwrap_or(8080);
+            server::run(port)
+        };
+        #[cfg(not(feature = "server"))]
+        let result: std::io::Result<()> =
+            Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "this build was compiled without the \"server\" feature"));
+        result.unwrap_or_else(|e| error(&e.to_string()));
+        return Ok(());
+    }
 
-fn do_stuff(args: Vec<String>) -> Result<(), reqwest::Error> {
     if args.len() < 3 {
         usage()
     }
@@ -37,29 +302,318 @@ fn do_stuff(args: Vec<String>) -> Result<(), reqwest::Error> {
             }
         },
         "departures" => {
+            let cache_key = if args.len() == 4 {
+                format!("{}_{}", args[2], args[3])
+            } else {
+                args[2].clone()
+            };
+            if let Some(interval) = watch_interval {
+                let fetch_board = |deps: Result<Departures, reqwest::Error>| deps.map(|mut deps| {
+                    if accessible_only {
+                        deps.departures.retain(|d| d.lowfloor);
+                    }
+                    deps
+                });
+                return match args.len() {
+                    3 => watch(interval, &abbr, plain_speech_format, lang, tz, smooth, max_age, || fetch_board(fetch_with_fallback(&cache_key, no_stale, || departures_by_stop(&args[2])))),
+                    4 => watch(interval, &abbr, plain_speech_format, lang, tz, smooth, max_age, || fetch_board(fetch_with_fallback(&cache_key, no_stale, || departures_by_route(&args[2], &args[3])))),
+                    _ => usage(),
+                };
+            }
             let deps = match args.len() {
-                3 => departures_by_stop(&args[2])?,
-                4 => departures_by_route(&args[2], &args[3])?,
+                3 => fetch_with_fallback(&cache_key, no_stale, || departures_by_stop(&args[2]))?,
+                4 => fetch_with_fallback(&cache_key, no_stale, || departures_by_route(&args[2], &args[3]))?,
                 _ => usage(),
             };
-            println!("{}", deps.stop_name);
-            for dep in deps.departures {
-                println!("{}", dep);
+            warn_if_stale(&deps, max_age);
+            if svg_format {
+                print!("{}", svg::render(&deps));
+            } else {
+                println!("{}", deps.stop_name);
+                if accessible_only {
+                    print_departures(deps.accessible_only(), &abbr, plain_speech_format, lang, tz);
+                } else {
+                    print_departures(&deps.departures, &abbr, plain_speech_format, lang, tz);
+                }
+            }
+        }
+        "next" => {
+            let stop_id = &args[2];
+            let route = args.iter().position(|a| a == "--route").and_then(|p| args.get(p + 1)).cloned();
+            let to = args.iter().position(|a| a == "--to").and_then(|p| args.get(p + 1)).cloned();
+            let print_as = args.iter().position(|a| a == "--print").and_then(|p| args.get(p + 1)).cloned().unwrap_or_else(|| "minutes".to_owned());
+
+            let deps = departures_by_stop(stop_id)?;
+            let next = deps.departures.iter()
+                .filter(|d| route.as_ref().map_or(true, |r| &d.route == r))
+                .filter(|d| to.as_ref().map_or(true, |t| d.destination.terminus.eq_ignore_ascii_case(t)))
+                .min_by_key(|d| d.time);
+
+            match next {
+                Some(dep) => {
+                    match &print_as[..] {
+                        "epoch" => println!("{}", dep.time.timestamp()),
+                        "iso" => println!("{}", dep.time.to_rfc3339()),
+                        _ => println!("{}", dep.time.signed_duration_since(Local::now()).num_minutes()),
+                    }
+                }
+                None => std::process::exit(3),
             }
         }
         "luckysearch" => {
             let query = &args[2..].join(" ");
             match search_by_name(query)?.iter().nth(0) {
                 Some(s) => {
-                    let deps = departures_by_stop(&s.id)?;
+                    let deps = fetch_with_fallback(&s.id, no_stale, || departures_by_stop(&s.id))?;
                     println!("{}", deps.stop_name);
-                    for dep in deps.departures {
-                        println!("{}", dep);
+                    if accessible_only {
+                        print_departures(deps.accessible_only(), &abbr, plain_speech_format, lang, tz);
+                    } else {
+                        print_departures(&deps.departures, &abbr, plain_speech_format, lang, tz);
                     }
                 },
-                None => error(&format!("Could  not find any stop matching \"{}\"", query)),
+                None => error(&lang.not_found_stop(query)),
+            }
+        }
+        "history" => {
+            let stop_id = &args[2];
+            let route = args.iter().position(|a| a == "--route").and_then(|p| args.get(p + 1)).cloned();
+            let since = args.iter().position(|a| a == "--since").and_then(|p| args.get(p + 1)).map_or_else(
+                || since_from_arg("today"),
+                |s| since_from_arg(s),
+            );
+            let rec = Recorder::open(recorder::default_db_path()).unwrap_or_else(|e| error(&e.to_string()));
+            let observations = rec.history(stop_id, route.as_ref().map(|s| s.as_str()), since).unwrap_or_else(|e| error(&e.to_string()));
+            for obs in observations {
+                println!(
+                    "{} {:<3} {:<20} observed {} predicted {}{}",
+                    obs.stop_id, obs.route, obs.destination,
+                    obs.observed_at.format("%Y-%m-%d %H:%M"),
+                    obs.predicted_time.format("%H:%M"),
+                    if obs.realtime {" *"} else {""},
+                );
+            }
+        }
+        "stop" => {
+            if args.len() < 4 || args[2] != "info" {
+                usage()
+            }
+            let stop_id = &args[3];
+            match search_by_stop_id(stop_id)? {
+                Some(stop) => {
+                    println!("{}", stop);
+                    match ZoneMap::load_csv(paths::fare_zones_file()) {
+                        Ok(zones) => {
+                            let zones = zones.zones_for(stop_id);
+                            if zones.is_empty() {
+                                println!("fare zone: unknown");
+                            } else {
+                                println!("fare zone: {}", zones.join(", "));
+                            }
+                        }
+                        Err(_) => println!("fare zone: unknown (no fare zone map loaded)"),
+                    }
+                    if args.iter().any(|a| a == "--image") {
+                        let url = staticmap::url_for_stop(&stop, 16, 600, 400);
+                        println!("map: {}", url);
+                    }
+                    if args.iter().any(|a| a == "--schedule") {
+                        let today = chrono::Local::now().naive_local().date();
+                        match schedule::service_span(stop_id, today) {
+                            Ok(spans) => {
+                                for span in spans {
+                                    println!("{:<5} first {} last {}", span.route, span.first.format("%H:%M"), span.last.format("%H:%M"));
+                                }
+                            }
+                            Err(e) => println!("schedule: {}", e),
+                        }
+                    }
+                }
+                None => error(&format!("Could not find stop \"{}\"", stop_id)),
             }
         }
+        "fare" => {
+            if args.len() < 4 {
+                usage()
+            }
+            let stop_a = &args[2];
+            let stop_b = &args[3];
+            let ticket_type = match args.iter().position(|a| a == "--ticket").and_then(|p| args.get(p + 1)).map(|s| s.as_str()) {
+                Some("tageskarte") => TicketType::Tageskarte,
+                _ => TicketType::Einzelfahrt,
+            };
+            let zones = ZoneMap::load_csv(paths::fare_zones_file()).unwrap_or_else(|e| error(&e.to_string()));
+            match fare::estimate_fare(&zones, stop_a, stop_b, ticket_type) {
+                Some(estimate) => {
+                    println!("{} (Waben: {})", estimate.ticket_name, estimate.zones.join(", "));
+                    println!("unofficial estimate, based on Wabe count only — check the current KVV tariff before buying");
+                }
+                None => error("fare zone unknown for one or both stops"),
+            }
+        }
+        "map" => {
+            let lat = args.iter().position(|a| a == "--lat").and_then(|p| args.get(p + 1)).and_then(|s| s.parse::<f64>().ok());
+            let lon = args.iter().position(|a| a == "--lon").and_then(|p| args.get(p + 1)).and_then(|s| s.parse::<f64>().ok());
+            let radius = args
+                .iter()
+                .position(|a| a == "--radius")
+                .and_then(|p| args.get(p + 1))
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(1.0);
+            let (lat, lon) = match (lat, lon) {
+                (Some(lat), Some(lon)) => (lat, lon),
+                _ => usage(),
+            };
+            let stops = search_by_latlon(lat, lon)?;
+            print!("{}", map::render(lat, lon, &stops, radius));
+        }
+        "qr" => {
+            if args.len() < 3 {
+                usage()
+            }
+            let stop_id = &args[2];
+            if let Some(pos) = args.iter().position(|a| a == "--output") {
+                let path = args.get(pos + 1).unwrap_or_else(|| usage());
+                std::fs::write(path, qr::render_png(stop_id)).unwrap_or_else(|e| error(&e.to_string()));
+            } else {
+                println!("{}", qr::render_terminal(stop_id));
+                println!("{}", qr::web_url(stop_id));
+            }
+        }
+        "commute" => {
+            if args.len() < 3 {
+                usage()
+            }
+            let profile_name = &args[2];
+            let config = Config::load_default().unwrap_or_else(|e| error(&format!("could not load config: {}", e)));
+            let profile = config.profile(profile_name).unwrap_or_else(|| error(&format!("no such profile: {}", profile_name)));
+            let stop_id = profile.stop_id.clone().unwrap_or_else(|| error(&format!("profile \"{}\" has no stop_id", profile_name)));
+
+            let mut deps = departures_by_stop(&stop_id)?;
+            deps.departures.retain(|d| profile.route.as_ref().map_or(true, |r| &d.route == r));
+            deps.departures.retain(|d| profile.destination.as_ref().map_or(true, |dest| d.destination.terminus.eq_ignore_ascii_case(dest)));
+
+            println!("{}", deps.stop_name);
+            print_departures(&deps.departures, &abbr, plain_speech_format, lang, tz);
+
+            let walk_minutes = profile.walk_minutes.unwrap_or(0);
+            let now = Local::now().with_timezone(&chrono_tz::Europe::Berlin);
+            if let Some(leave_time) = leave::next_leave_time(&deps, profile.route.as_deref(), profile.destination.as_deref(), walk_minutes, now) {
+                println!(
+                    "leave by {} for {} toward {} ({})",
+                    leave_time.leave_at.format("%H:%M"),
+                    leave_time.route,
+                    leave_time.destination,
+                    leave_time.departure_time.format("%H:%M"),
+                );
+            }
+        }
+        "transfer" => {
+            if args.len() < 4 {
+                usage()
+            }
+            let stop_a = args[2].clone();
+            let stop_b = args[3].clone();
+            let route_a = args.iter().position(|a| a == "--route-a").and_then(|p| args.get(p + 1)).cloned();
+            let route_b = args.iter().position(|a| a == "--route-b").and_then(|p| args.get(p + 1)).cloned();
+            let in_vehicle_minutes = args
+                .iter()
+                .position(|a| a == "--in-vehicle")
+                .and_then(|p| args.get(p + 1))
+                .and_then(|s| s.parse::<i64>().ok())
+                .unwrap_or_else(|| error("--in-vehicle is required, e.g. --in-vehicle 12"));
+            let min_connection_minutes = args
+                .iter()
+                .position(|a| a == "--buffer")
+                .and_then(|p| args.get(p + 1))
+                .and_then(|s| s.parse::<i64>().ok())
+                .unwrap_or(3);
+            let plan = TransferPlan { in_vehicle_minutes, min_connection_minutes };
+
+            let check_once = || -> Result<TransferStatus, reqwest::Error> {
+                let board_a = departures_by_stop(&stop_a)?;
+                let board_b = departures_by_stop(&stop_b)?;
+                Ok(transfer::check_transfer(&board_a, route_a.as_deref(), &plan, &board_b, route_b.as_deref()).unwrap_or(TransferStatus::Missed))
+            };
+            let print_status = |status: TransferStatus| match status {
+                TransferStatus::Comfortable(slack) => println!("transfer OK, {} min slack", slack),
+                TransferStatus::Tight(slack) => println!("transfer TIGHT, only {} min slack", slack),
+                TransferStatus::Missed => println!("transfer MISSED, no connecting departure in time"),
+            };
+
+            match watch_interval {
+                Some(interval) => loop {
+                    print_status(check_once()?);
+                    thread::sleep(StdDuration::from_secs(interval));
+                },
+                None => print_status(check_once()?),
+            }
+        }
+        "leave" => {
+            if args.len() < 3 {
+                usage()
+            }
+            let stop_id = &args[2];
+            let route = args.iter().position(|a| a == "--route").and_then(|p| args.get(p + 1)).cloned();
+            let to = args.iter().position(|a| a == "--to").and_then(|p| args.get(p + 1)).cloned();
+            let walk_minutes = args
+                .iter()
+                .position(|a| a == "--walk")
+                .and_then(|p| args.get(p + 1))
+                .map(|s| s.trim_end_matches('m'))
+                .and_then(|s| s.parse::<i64>().ok())
+                .unwrap_or_else(|| error("--walk is required, e.g. --walk 6m"));
+
+            let deps = departures_by_stop(stop_id)?;
+            let now = Local::now().with_timezone(&chrono_tz::Europe::Berlin);
+            match leave::next_leave_time(&deps, route.as_deref(), to.as_deref(), walk_minutes, now) {
+                Some(leave_time) => println!(
+                    "leave by {} for {} toward {} ({})",
+                    leave_time.leave_at.format("%H:%M"),
+                    leave_time.route,
+                    leave_time.destination,
+                    leave_time.departure_time.format("%H:%M"),
+                ),
+                None => error("no catchable departure found"),
+            }
+        }
+        "ask" => {
+            let query = args[2..].join(" ");
+            let parsed = nlquery::parse(&query);
+            let stop_name = match &parsed.stop {
+                Some(s) => s,
+                None => error("could not find a stop name in that question"),
+            };
+            let stop = match search_by_name(stop_name)?.into_iter().next() {
+                Some(s) => s,
+                None => error(&lang.not_found_stop(stop_name)),
+            };
+            let mut deps = departures_by_stop(&stop.id)?;
+            if let Some(route) = &parsed.route {
+                deps.departures.retain(|d| d.route.eq_ignore_ascii_case(route));
+            }
+            if let Some(destination) = &parsed.destination {
+                let destination = destination.to_lowercase();
+                deps.departures.retain(|d| d.destination.terminus.to_lowercase().contains(&destination));
+            }
+            println!("{}", deps.summarize(lang));
+        }
+        "stats" => {
+            let stop_id = &args[2];
+            let rec = Recorder::open(recorder::default_db_path()).unwrap_or_else(|e| error(&e.to_string()));
+            let observations = rec.all(stop_id).unwrap_or_else(|e| error(&e.to_string()));
+            let summary = stats::summarize(&observations);
+
+            println!("{} observations, {:.0}% realtime coverage", summary.total_observations, summary.realtime_coverage * 100.0);
+            println!();
+            println!("observations per route:");
+            for (route, count) in &summary.observations_per_route {
+                println!("  {:<5} {:<4} {}", route, count, stats::bar_chart(&[*count]));
+            }
+            println!();
+            println!("observations per hour of day:");
+            println!("  {}", stats::bar_chart(&summary.observations_per_hour));
+        }
         _ => usage(),
     }
     Ok(())
@@ -74,13 +628,46 @@ fn usage() -> ! {
     let usage = r#"Usage:
   kvvliveapi search (NAME|STOP_ID)
   kvvliveapi search LAT LON
-  kvvliveapi departures STOP_ID [ROUTE]
-  kvvliveapi luckysearch NAME"#;
+  kvvliveapi departures STOP_ID [ROUTE] [--accessible] [--max-dest-len N] [--format plain-speech|svg] [--watch [SECONDS]]
+  kvvliveapi luckysearch NAME [--accessible] [--max-dest-len N] [--format plain-speech]
+  kvvliveapi next STOP_ID [--route ROUTE] [--to DESTINATION] [--print minutes|epoch|iso]
+  kvvliveapi history STOP_ID [--route ROUTE] [--since today|yesterday]
+  kvvliveapi stats STOP_ID
+  kvvliveapi stop info STOP_ID [--image] [--schedule]
+  kvvliveapi fare STOP_ID_A STOP_ID_B [--ticket einzelfahrt|tageskarte]
+  kvvliveapi map --lat LAT --lon LON [--radius KM]
+  kvvliveapi qr STOP_ID [--output FILE.png]
+  kvvliveapi ask "QUESTION"
+  kvvliveapi leave STOP_ID --walk MINUTESm [--route ROUTE] [--to DESTINATION]
+  kvvliveapi commute PROFILE_NAME
+  kvvliveapi transfer STOP_A STOP_B --in-vehicle MINUTES [--buffer MINUTES] [--route-a ROUTE] [--route-b ROUTE] [--watch [SECONDS]]
+  kvvliveapi rpc          speak JSON-RPC 2.0 (searchStops, getDepartures, subscribe) on stdin/stdout
+  kvvliveapi server [--port PORT]   run a REST server (GET /stops, /departures/{stopId}, /openapi.json)
+  kvvliveapi doctor [STOP_ID]   check connectivity, API key, config file, cache directory, and (given a STOP_ID) clock sanity
+
+  --accessible            only show departures served by low-floor vehicles
+  --max-dest-len N        shorten destinations to at most N characters
+  --format plain-speech   print full sentences, for screen readers and TTS
+  --lang de|en            output language for generated strings (default: from $LANG)
+  --timeout SECONDS       request timeout (default: 30)
+  --retries N             number of retries on a failed request (default: 0)
+  --no-gzip               disable gzip/deflate response compression (on by default)
+  --no-stale              fail instead of falling back to a cached board when offline
+  --watch [SECONDS]       keep polling and redraw, highlighting changes (default interval: 20s)
+  --smooth                with --watch, delay backwards countdown jumps until confirmed by a later poll
+  --max-age SECONDS      warn on stderr if the board is older than this (default: 120)
+  --tz TIMEZONE          IANA zone name (e.g. UTC, Europe/Berlin) for clock-time columns (default: Europe/Berlin)
+
+  `next` prints one value and exits with status 3 if nothing matches the filters."#;
     println!("{}", usage);
     std::process::exit(1);
 }
 
 fn main() {
+    // no-op outside Windows; legacy `cmd.exe`/PowerShell consoles otherwise
+    // print our color/highlighting escape codes literally
+    let _ = enable_ansi_support::enable_ansi_support();
+
     let args = args().collect::<Vec<_>>();
     do_stuff(args).unwrap_or_else(|e| error(e.description()));
 }
@@ -0,0 +1,76 @@
+//! A rough ASCII scatter plot of nearby stops, for orientation when
+//! there's no room (or no will) for an actual map: SSH'd into a box, a
+//! feature-phone terminal, or just a quick "what's around me" glance.
+
+use term;
+use Stop;
+
+const DEFAULT_COLS: usize = 61;
+const MIN_COLS: usize = 21;
+
+/// Approximate km per degree of longitude at `lat`, accounting for the
+/// fact that meridians converge toward the poles.
+fn km_per_degree_lon(lat: f64) -> f64 {
+    111.32 * lat.to_radians().cos()
+}
+
+const KM_PER_DEGREE_LAT: f64 = 111.32;
+
+/// Offset of `(lat, lon)` from `(center_lat, center_lon)`, in km, as
+/// (east, north).
+fn offset_km(center_lat: f64, center_lon: f64, lat: f64, lon: f64) -> (f64, f64) {
+    let east = (lon - center_lon) * km_per_degree_lon(center_lat);
+    let north = (lat - center_lat) * KM_PER_DEGREE_LAT;
+    (east, north)
+}
+
+/// Render an ASCII scatter plot of `stops` within `radius_km` of
+/// `(center_lat, center_lon)`, with `@` marking the center and each stop
+/// labeled by a letter keyed in a legend below the grid.
+///
+/// The grid is sized to the terminal width when known, falling back to
+/// [`DEFAULT_COLS`] columns otherwise; stops outside the radius, or that
+/// would overlap an already-plotted cell, are skipped.
+pub fn render(center_lat: f64, center_lon: f64, stops: &[Stop], radius_km: f64) -> String {
+    let cols = term::width().map(|w| (w as usize).clamp(MIN_COLS, DEFAULT_COLS)).unwrap_or(DEFAULT_COLS);
+    let rows = (cols / 3) | 1; // odd, so there's a single center row
+
+    let mut grid = vec![vec![' '; cols]; rows];
+    let center_col = cols / 2;
+    let center_row = rows / 2;
+    grid[center_row][center_col] = '@';
+
+    let mut legend = Vec::new();
+    for stop in stops {
+        let (east, north) = offset_km(center_lat, center_lon, stop.lat, stop.lon);
+        if east.abs() > radius_km || north.abs() > radius_km {
+            continue;
+        }
+
+        let col = center_col as isize + (east / radius_km * center_col as f64).round() as isize;
+        let row = center_row as isize - (north / radius_km * center_row as f64).round() as isize;
+        if col < 0 || row < 0 || col as usize >= cols || row as usize >= rows {
+            continue;
+        }
+        let (col, row) = (col as usize, row as usize);
+        if grid[row][col] != ' ' {
+            continue;
+        }
+
+        let letter = (b'a' + (legend.len() % 26) as u8) as char;
+        grid[row][col] = letter;
+        legend.push((letter, stop.name.clone()));
+    }
+
+    let mut out = String::new();
+    for line in &grid {
+        out.push_str(&line.iter().collect::<String>());
+        out.push('\n');
+    }
+    out.push('\n');
+    out.push_str("@ = you\n");
+    for (letter, name) in &legend {
+        out.push_str(&format!("{} = {}\n", letter, name));
+    }
+    out
+}
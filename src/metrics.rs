@@ -0,0 +1,84 @@
+//! Client-side request metrics: counts, error rate, average latency, and
+//! cache hit ratio, so operators of boards/bots built on this crate can
+//! monitor upstream health without their own instrumentation.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+static TOTAL_REQUESTS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_ERRORS: AtomicU64 = AtomicU64::new(0);
+static TOTAL_LATENCY_MS: AtomicU64 = AtomicU64::new(0);
+static CACHE_HITS: AtomicU64 = AtomicU64::new(0);
+static CACHE_MISSES: AtomicU64 = AtomicU64::new(0);
+static NOT_MODIFIED: AtomicU64 = AtomicU64::new(0);
+static MODIFIED: AtomicU64 = AtomicU64::new(0);
+
+fn requests_by_endpoint() -> &'static Mutex<HashMap<String, u64>> {
+    static MAP: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
+    MAP.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record the outcome of one upstream HTTP request, for [`snapshot`].
+pub fn record_request(endpoint: &str, latency_ms: u64, success: bool) {
+    TOTAL_REQUESTS.fetch_add(1, Ordering::Relaxed);
+    TOTAL_LATENCY_MS.fetch_add(latency_ms, Ordering::Relaxed);
+    if !success {
+        TOTAL_ERRORS.fetch_add(1, Ordering::Relaxed);
+    }
+    *requests_by_endpoint().lock().unwrap().entry(endpoint.to_owned()).or_insert(0) += 1;
+}
+
+/// Record a cache lookup outcome, for [`snapshot`]'s cache hit ratio.
+pub fn record_cache_lookup(hit: bool) {
+    if hit {
+        CACHE_HITS.fetch_add(1, Ordering::Relaxed);
+    } else {
+        CACHE_MISSES.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Record whether a conditional request for a board came back as `304
+/// Not Modified`, for [`snapshot`]'s `not_modified_ratio`.
+pub fn record_not_modified(not_modified: bool) {
+    if not_modified {
+        NOT_MODIFIED.fetch_add(1, Ordering::Relaxed);
+    } else {
+        MODIFIED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// A point-in-time view of accumulated request metrics for this process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub total_requests: u64,
+    pub total_errors: u64,
+    pub requests_by_endpoint: HashMap<String, u64>,
+    /// mean latency in milliseconds across all recorded requests
+    pub avg_latency_ms: f64,
+    /// fraction (0.0..=1.0) of cache lookups that were hits
+    pub cache_hit_ratio: f64,
+    /// fraction (0.0..=1.0) of conditional requests answered with a `304
+    /// Not Modified` instead of a full body
+    pub not_modified_ratio: f64,
+}
+
+/// Take a snapshot of the metrics accumulated so far in this process.
+pub fn snapshot() -> Snapshot {
+    let total_requests = TOTAL_REQUESTS.load(Ordering::Relaxed);
+    let total_errors = TOTAL_ERRORS.load(Ordering::Relaxed);
+    let total_latency_ms = TOTAL_LATENCY_MS.load(Ordering::Relaxed);
+    let cache_hits = CACHE_HITS.load(Ordering::Relaxed);
+    let cache_misses = CACHE_MISSES.load(Ordering::Relaxed);
+    let not_modified = NOT_MODIFIED.load(Ordering::Relaxed);
+    let modified = MODIFIED.load(Ordering::Relaxed);
+
+    Snapshot {
+        total_requests,
+        total_errors,
+        requests_by_endpoint: requests_by_endpoint().lock().unwrap().clone(),
+        avg_latency_ms: if total_requests == 0 { 0.0 } else { total_latency_ms as f64 / total_requests as f64 },
+        cache_hit_ratio: if cache_hits + cache_misses == 0 { 0.0 } else { cache_hits as f64 / (cache_hits + cache_misses) as f64 },
+        not_modified_ratio: if not_modified + modified == 0 { 0.0 } else { not_modified as f64 / (not_modified + modified) as f64 },
+    }
+}
@@ -0,0 +1,55 @@
+//! A small German/English grammar for turning a free-text question like
+//! "wann fährt die nächste S2 am Marktplatz Richtung Durlach" into a stop
+//! name plus route/destination filters.
+//!
+//! This is keyword-based pattern matching, not general natural-language
+//! understanding: it looks for a short alphanumeric token to use as the
+//! route, and phrases following a small set of prepositions ("am"/"an"/
+//! "at" for the stop, "nach"/"richtung"/"to"/"toward" for the
+//! destination). Good enough for a CLI one-liner or a chat bot's first
+//! pass; anything more ambiguous should fall back to `search`/`next`.
+
+use regex::Regex;
+
+/// The stop, route, and destination extracted from a free-text query.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedQuery {
+    pub stop: Option<String>,
+    pub route: Option<String>,
+    pub destination: Option<String>,
+}
+
+const STOP_KEYWORDS: &[&str] = &["am", "an", "at", "stop", "haltestelle"];
+const DESTINATION_KEYWORDS: &[&str] = &["nach", "richtung", "to", "toward"];
+
+fn all_keywords() -> impl Iterator<Item = &'static &'static str> {
+    STOP_KEYWORDS.iter().chain(DESTINATION_KEYWORDS.iter())
+}
+
+/// Find the phrase following the first occurrence of any of `keywords`,
+/// stopping at the next recognized keyword or the end of the query.
+fn extract_phrase(tokens: &[&str], keywords: &[&str]) -> Option<String> {
+    let start = tokens.iter().position(|t| keywords.iter().any(|k| t.eq_ignore_ascii_case(k)))?;
+    let phrase: Vec<&str> = tokens[start + 1..]
+        .iter()
+        .take_while(|t| !all_keywords().any(|k| t.eq_ignore_ascii_case(k)))
+        .cloned()
+        .collect();
+    if phrase.is_empty() {
+        None
+    } else {
+        Some(phrase.join(" "))
+    }
+}
+
+/// Parse a free-text query into a stop/route/destination filter.
+pub fn parse(query: &str) -> ParsedQuery {
+    let route_re = Regex::new(r"^[A-Za-z]{0,3}\d{1,3}$").unwrap();
+    let tokens: Vec<&str> = query.split_whitespace().collect();
+
+    let route = tokens.iter().find(|t| route_re.is_match(t)).map(|s| s.to_string());
+    let stop = extract_phrase(&tokens, STOP_KEYWORDS);
+    let destination = extract_phrase(&tokens, DESTINATION_KEYWORDS);
+
+    ParsedQuery { stop, route, destination }
+}
@@ -0,0 +1,192 @@
+//! Generic webhook notifications: POST a JSON payload to a configurable URL
+//! when something worth telling someone about happens (a departure getting
+//! close, a likely cancellation, ...).
+
+use std::fmt::{self, Display};
+
+use chrono::Local;
+use reqwest::Client;
+use serde_json::json;
+
+use Departure;
+
+/// Error returned by a [`Notifier`]. Currently every built-in notifier only
+/// ever fails at the HTTP layer, but the error is its own type so adding
+/// notifiers that fail in other ways later isn't a breaking change.
+#[derive(Debug)]
+pub enum NotifyError {
+    Request(reqwest::Error),
+}
+
+impl From<reqwest::Error> for NotifyError {
+    fn from(e: reqwest::Error) -> Self {
+        NotifyError::Request(e)
+    }
+}
+
+impl Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NotifyError::Request(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// A channel that can be told about a departure worth telling someone
+/// about. Implement this to plug in notification channels the crate
+/// doesn't ship built-in support for (Matrix, Slack, XMPP, ...) while
+/// reusing the crate's rule evaluation and board-watching machinery.
+pub trait Notifier {
+    fn notify(&self, departure: &Departure) -> Result<(), NotifyError>;
+}
+
+/// Posts a JSON payload to a fixed URL whenever [`notify`](WebhookNotifier::notify)
+/// is called. The payload defaults to a small fixed shape, or can be
+/// replaced with a template containing `{{route}}`, `{{destination}}`,
+/// `{{time}}`, and `{{minutes}}` placeholders.
+pub struct WebhookNotifier {
+    url: String,
+    template: Option<String>,
+}
+
+impl WebhookNotifier {
+    /// Create a notifier posting to `url`.
+    pub fn new(url: &str) -> WebhookNotifier {
+        WebhookNotifier { url: url.to_owned(), template: None }
+    }
+
+    /// Use a custom JSON payload template instead of the default shape.
+    /// `{{route}}`, `{{destination}}`, `{{time}}`, and `{{minutes}}` are
+    /// substituted with values from the departure being notified about.
+    pub fn with_template(mut self, template: &str) -> WebhookNotifier {
+        self.template = Some(template.to_owned());
+        self
+    }
+
+    fn render(&self, departure: &Departure) -> String {
+        let minutes = departure.time.signed_duration_since(Local::now()).num_minutes();
+        match self.template {
+            Some(ref template) => template
+                .replace("{{route}}", &departure.route)
+                .replace("{{destination}}", &departure.destination.terminus)
+                .replace("{{time}}", &departure.time.to_rfc3339())
+                .replace("{{minutes}}", &minutes.to_string()),
+            None => json!({
+                "route": departure.route,
+                "destination": departure.destination.terminus,
+                "time": departure.time.to_rfc3339(),
+                "minutes": minutes,
+            })
+            .to_string(),
+        }
+    }
+
+}
+
+impl Notifier for WebhookNotifier {
+    /// POST the rendered payload for `departure` to the configured URL.
+    fn notify(&self, departure: &Departure) -> Result<(), NotifyError> {
+        Client::new()
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(self.render(departure))
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+fn default_message(departure: &Departure) -> String {
+    let minutes = departure.time.signed_duration_since(Local::now()).num_minutes();
+    format!("{} to {} in {} min", departure.route, departure.destination.terminus, minutes)
+}
+
+/// Sends phone push notifications via [ntfy.sh](https://ntfy.sh), no
+/// account or custom receiver required: just a topic name.
+pub struct NtfyNotifier {
+    topic: String,
+    server: String,
+}
+
+impl NtfyNotifier {
+    /// Notify on the public ntfy.sh server's `topic`.
+    pub fn new(topic: &str) -> NtfyNotifier {
+        NtfyNotifier { topic: topic.to_owned(), server: "https://ntfy.sh".to_owned() }
+    }
+
+    /// Use a self-hosted ntfy server instead of the public one.
+    pub fn with_server(mut self, server: &str) -> NtfyNotifier {
+        self.server = server.trim_end_matches('/').to_owned();
+        self
+    }
+
+}
+
+impl Notifier for NtfyNotifier {
+    fn notify(&self, departure: &Departure) -> Result<(), NotifyError> {
+        Client::new()
+            .post(&format!("{}/{}", self.server, self.topic))
+            .body(default_message(departure))
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Sends phone push notifications via [Pushover](https://pushover.net).
+pub struct PushoverNotifier {
+    token: String,
+    user: String,
+}
+
+impl PushoverNotifier {
+    /// Notify `user` using the application `token`.
+    pub fn new(token: &str, user: &str) -> PushoverNotifier {
+        PushoverNotifier { token: token.to_owned(), user: user.to_owned() }
+    }
+
+}
+
+impl Notifier for PushoverNotifier {
+    fn notify(&self, departure: &Departure) -> Result<(), NotifyError> {
+        Client::new()
+            .post("https://api.pushover.net/1/messages.json")
+            .form(&[
+                ("token", self.token.as_str()),
+                ("user", self.user.as_str()),
+                ("message", &default_message(departure)),
+            ])
+            .send()?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+    use Destination;
+
+    fn departure_with_destination(terminus: &str) -> Departure {
+        Departure::new(
+            "S2",
+            Destination::new(terminus, Vec::new(), terminus),
+            "1",
+            Berlin.with_ymd_and_hms(2026, 8, 9, 12, 0, 0).unwrap(),
+            false,
+            true,
+            0,
+        )
+    }
+
+    #[test]
+    fn default_payload_is_valid_json_even_with_quotes_in_destination() {
+        let notifier = WebhookNotifier::new("https://example.com/hook");
+        let departure = departure_with_destination(r#"Karlsruhe "Hbf""#);
+        let rendered = notifier.render(&departure);
+        let value: serde_json::Value = serde_json::from_str(&rendered).expect("payload must be valid JSON");
+        assert_eq!(value["destination"], r#"Karlsruhe "Hbf""#);
+    }
+}
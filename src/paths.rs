@@ -0,0 +1,41 @@
+//! Where the crate's persisted state lives on disk, following the
+//! `XDG_CONFIG_HOME`/`XDG_CACHE_HOME`/`XDG_DATA_HOME` conventions on Linux
+//! (with the platform-appropriate equivalents on Windows and macOS) instead
+//! of dumping everything into the system temp directory.
+
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+fn project_dirs() -> ProjectDirs {
+    ProjectDirs::from("de", "nervengiftlabs", "kvvliveapi")
+        .expect("could not determine a home directory for config/cache/data paths")
+}
+
+/// Directory for user-editable configuration (e.g. `config.toml`).
+pub fn config_dir() -> PathBuf {
+    project_dirs().config_dir().to_path_buf()
+}
+
+/// Directory for disposable cached data (e.g. the last-known-good board
+/// per stop).
+pub fn cache_dir() -> PathBuf {
+    project_dirs().cache_dir().to_path_buf()
+}
+
+/// Directory for persistent application data (e.g. the recorder
+/// database).
+pub fn data_dir() -> PathBuf {
+    project_dirs().data_dir().to_path_buf()
+}
+
+/// Default path of the configuration file.
+pub fn config_file() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// Default path of the user-supplied fare zone (Waben) map, see
+/// [`fare`](::fare).
+pub fn fare_zones_file() -> PathBuf {
+    data_dir().join("fare_zones.csv")
+}
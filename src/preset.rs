@@ -0,0 +1,51 @@
+//! EFA backend presets. The KVV live API is one deployment of the common
+//! EFA ("Elektronische Fahrplanauskunft") system used by many German
+//! transit networks; everything this crate does is reusable against
+//! another EFA deployment once you know its base URL and API key.
+//!
+//! The crate only ships a preset for KVV itself, since it's the only
+//! endpoint this crate has been tested against. Construct an
+//! [`EfaPreset`] with [`EfaPreset::new`] to point the client at another
+//! network (e.g. VRN, VVS, VAG, DING) and [`set_active`] it.
+
+use std::sync::{Mutex, OnceLock};
+
+const KVV_API_KEY: &str = "377d840e54b59adbe53608ba1aad70e8";
+const KVV_API_BASE: &str = "https://live.kvv.de/webapp/";
+
+/// A base URL and API key identifying one EFA deployment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EfaPreset {
+    pub name: String,
+    pub base_url: String,
+    pub key: String,
+}
+
+impl EfaPreset {
+    /// Define a preset for an EFA deployment other than KVV.
+    pub fn new(name: &str, base_url: &str, key: &str) -> EfaPreset {
+        EfaPreset { name: name.to_owned(), base_url: base_url.to_owned(), key: key.to_owned() }
+    }
+
+    /// The Karlsruher Verkehrsverbund, this crate's original and
+    /// best-tested backend.
+    pub fn kvv() -> EfaPreset {
+        EfaPreset::new("KVV", KVV_API_BASE, KVV_API_KEY)
+    }
+}
+
+fn active_slot() -> &'static Mutex<EfaPreset> {
+    static ACTIVE: OnceLock<Mutex<EfaPreset>> = OnceLock::new();
+    ACTIVE.get_or_init(|| Mutex::new(EfaPreset::kvv()))
+}
+
+/// Switch every subsequent call in this process to a different EFA
+/// deployment.
+pub fn set_active(preset: EfaPreset) {
+    *active_slot().lock().unwrap() = preset;
+}
+
+/// The EFA deployment currently in use (KVV by default).
+pub fn active() -> EfaPreset {
+    active_slot().lock().unwrap().clone()
+}
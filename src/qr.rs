@@ -0,0 +1,31 @@
+//! Rendering a QR code linking to the KVV web departure page for a stop,
+//! for printing next to a door or scanning from a phone instead of typing
+//! a stop id.
+
+use qrcode::render::unicode;
+use qrcode::QrCode;
+
+const KVV_WEB_BASE: &str = "https://www.kvv.de/fahrplan/fahrplanauskunft/abfahrtstafel.html";
+
+/// The KVV web departure page URL for a stop.
+pub fn web_url(stop_id: &str) -> String {
+    format!("{}?stop={}", KVV_WEB_BASE, stop_id)
+}
+
+/// Render a QR code linking to the stop's departure page as a block of
+/// unicode half-block characters, ready to print straight to a terminal.
+pub fn render_terminal(stop_id: &str) -> String {
+    let code = QrCode::new(web_url(stop_id)).expect("stop id produces a URL short enough to encode");
+    code.render::<unicode::Dense1x2>().quiet_zone(false).build()
+}
+
+/// Render the same QR code as a PNG, for `--output file.png`-style use.
+pub fn render_png(stop_id: &str) -> Vec<u8> {
+    let code = QrCode::new(web_url(stop_id)).expect("stop id produces a URL short enough to encode");
+    let image = code.render::<image::Luma<u8>>().build();
+    let mut bytes: Vec<u8> = Vec::new();
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+        .expect("encoding a freshly rendered QR code to PNG cannot fail");
+    bytes
+}
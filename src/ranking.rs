@@ -0,0 +1,87 @@
+//! Client-side re-ranking of stop search results.
+//!
+//! The API's own ordering is often unhelpful, so this re-scores results by
+//! edit distance (and a prefix-match bonus) against the original query,
+//! exposing the score so UIs can show confidence.
+
+use Stop;
+
+/// A search result annotated with its similarity score against the query.
+///
+/// Lower `distance` and higher `score` both mean a better match; `score` is
+/// normalized to `0.0..=1.0` so UIs can render it directly (e.g. as a
+/// confidence bar).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedStop {
+    /// the matched stop
+    pub stop: Stop,
+    /// Levenshtein distance between the query and the stop name
+    pub distance: usize,
+    /// normalized similarity score, `1.0` for an exact match
+    pub score: f64,
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Re-rank `stops` by similarity to `query`, most similar first.
+///
+/// A case-insensitive prefix match is always ranked above a non-prefix
+/// match with the same edit distance.
+pub fn rank_by_name(query: &str, stops: Vec<Stop>) -> Vec<RankedStop> {
+    let query_lower = query.to_lowercase();
+    let mut ranked: Vec<RankedStop> = stops
+        .into_iter()
+        .map(|stop| {
+            let name_lower = stop.name.to_lowercase();
+            let distance = levenshtein(&query_lower, &name_lower);
+            let longest = query_lower.chars().count().max(name_lower.chars().count()).max(1);
+            let mut score = 1.0 - (distance as f64 / longest as f64);
+            if name_lower.starts_with(&query_lower) {
+                score = (score + 0.25).min(1.0);
+            }
+            RankedStop { stop, distance, score }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Stop;
+
+    fn stop(name: &str) -> Stop {
+        Stop { name: name.to_owned(), id: "de:0:0".to_owned(), lat: 0.0, lon: 0.0 }
+    }
+
+    #[test]
+    fn ranks_exact_match_first() {
+        let stops = vec![stop("Karlsruhe Hauptfriedhof"), stop("Karlsruhe Marktplatz")];
+        let ranked = rank_by_name("Marktplatz", stops);
+        assert_eq!(ranked[0].stop.name, "Karlsruhe Marktplatz");
+    }
+
+    #[test]
+    fn prefix_match_beats_equal_distance_substring() {
+        let stops = vec![stop("Xab"), stop("abX")];
+        let ranked = rank_by_name("ab", stops);
+        assert_eq!(ranked[0].stop.name, "abX");
+    }
+}
@@ -0,0 +1,120 @@
+//! Wire-format mirrors of this crate's domain types ([`Departures`],
+//! [`Departure`], [`Stop`]), deserializing exactly what the API sends with
+//! none of [`Departure`]'s `deserialize_with` parsing — so a board that
+//! deserializes fine here can still fail to convert, and the conversion
+//! (`into_domain`) degrades one bad departure to a [`ParseIssue`] instead of
+//! failing to parse the whole response the way going straight to
+//! [`Departures`] does (still the default, and still the right choice
+//! when any malformed field should be a hard error).
+//!
+//! See [`client::KvvClient::departures_by_stop_lenient`](::client::KvvClient::departures_by_stop_lenient)
+//! and [`departures_by_stop_lenient`](::departures_by_stop_lenient) for the
+//! entry points that use this.
+
+use {Departure, Departures, Destination, Occupancy, Stop};
+
+/// One per-item problem surfaced by [`RawDepartures::into_domain`], e.g. a
+/// departure time the API sent in a format [`parse_departure_time_str`](::parse_departure_time_str)
+/// doesn't recognize.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseIssue {
+    /// index into the raw board's `departures` list this concerns, or
+    /// `None` if the problem was with the board itself (its timestamp)
+    pub departure_index: Option<usize>,
+    pub message: String,
+}
+
+/// Wire-format mirror of [`Stop`]. Since every one of `Stop`'s fields is
+/// already a primitive on the wire, converting is infallible.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawStop {
+    pub name: String,
+    pub id: String,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+impl RawStop {
+    pub fn into_domain(self) -> Stop {
+        Stop { name: self.name, id: self.id, lat: self.lat, lon: self.lon }
+    }
+}
+
+/// Wire-format mirror of [`Departure`]: `destination` and `time` are kept
+/// as the raw strings the API sent, unparsed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawDeparture {
+    pub route: String,
+    pub destination: String,
+    pub direction: String,
+    pub time: String,
+    pub lowfloor: bool,
+    pub realtime: bool,
+    pub traction: u32,
+    #[serde(default)]
+    pub platform: Option<String>,
+    #[serde(default)]
+    pub occupancy: Option<Occupancy>,
+}
+
+impl RawDeparture {
+    /// Convert to a [`Departure`], or an error describing the one field
+    /// that didn't parse.
+    pub fn into_domain(self) -> Result<Departure, String> {
+        let time = ::parse_departure_time_str(&self.time).map_err(|e| format!("time {:?}: {}", self.time, e))?;
+        Ok(Departure {
+            route: self.route,
+            destination: Destination::parse(&self.destination),
+            direction: self.direction,
+            time,
+            lowfloor: self.lowfloor,
+            realtime: self.realtime,
+            traction: self.traction,
+            platform: self.platform,
+            occupancy: self.occupancy,
+        })
+    }
+}
+
+/// Wire-format mirror of [`Departures`]: `timestamp` is kept as the raw
+/// string the API sent, unparsed, and `departures` holds [`RawDeparture`]s.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RawDepartures {
+    pub timestamp: String,
+    #[serde(rename = "stopName")]
+    pub stop_name: String,
+    pub departures: Vec<RawDeparture>,
+}
+
+impl RawDepartures {
+    /// Convert to a [`Departures`] board: a departure whose fields don't
+    /// convert is dropped with a [`ParseIssue`] rather than failing the
+    /// whole board, and a board timestamp that doesn't parse falls back
+    /// to the current time, also with a `ParseIssue`.
+    pub fn into_domain(self) -> (Departures, Vec<ParseIssue>) {
+        let mut issues = Vec::new();
+
+        let timestamp = match ::parse_timestamp_str(&self.timestamp) {
+            Ok(t) => t,
+            Err(e) => {
+                issues.push(ParseIssue { departure_index: None, message: format!("timestamp {:?}: {}", self.timestamp, e) });
+                ::chrono::Local::now().with_timezone(&::chrono_tz::Europe::Berlin)
+            }
+        };
+
+        let departures = self
+            .departures
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, raw)| match raw.into_domain() {
+                Ok(d) => Some(d),
+                Err(message) => {
+                    issues.push(ParseIssue { departure_index: Some(i), message });
+                    None
+                }
+            })
+            .collect();
+
+        (Departures { timestamp, stop_name: self.stop_name, departures }, issues)
+    }
+}
@@ -0,0 +1,112 @@
+//! SQLite-backed recording of observed boards, so tools (like the CLI's
+//! `history` and `stats` subcommands) can look back at how departures
+//! actually behaved rather than only what the live endpoint says right now.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use chrono_tz::Tz;
+use rusqlite::{params, Connection};
+
+use paths;
+use Departures;
+
+/// One recorded sighting of a departure on a particular board poll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub stop_id: String,
+    pub route: String,
+    pub destination: String,
+    pub direction: String,
+    /// when this observation was recorded
+    pub observed_at: DateTime<Tz>,
+    /// the predicted departure time as of this observation
+    pub predicted_time: DateTime<Tz>,
+    pub realtime: bool,
+}
+
+/// Default location for the recorder database.
+pub fn default_db_path() -> PathBuf {
+    paths::data_dir().join("recorder.sqlite3")
+}
+
+/// A handle to the recorder database.
+pub struct Recorder {
+    conn: Connection,
+}
+
+impl Recorder {
+    /// Open (creating if necessary) the recorder database at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> rusqlite::Result<Recorder> {
+        if let Some(dir) = path.as_ref().parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS observations (
+                stop_id        TEXT NOT NULL,
+                route          TEXT NOT NULL,
+                destination    TEXT NOT NULL,
+                direction      TEXT NOT NULL,
+                observed_at    TEXT NOT NULL,
+                predicted_time TEXT NOT NULL,
+                realtime       INTEGER NOT NULL
+            )",
+            params![],
+        )?;
+        Ok(Recorder { conn })
+    }
+
+    /// Record every departure currently on `board` for `stop_id`.
+    pub fn record(&self, stop_id: &str, board: &Departures) -> rusqlite::Result<()> {
+        let now = Local::now().with_timezone(&board.timestamp.timezone());
+        for dep in &board.departures {
+            self.conn.execute(
+                "INSERT INTO observations (stop_id, route, destination, direction, observed_at, predicted_time, realtime)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    stop_id,
+                    dep.route,
+                    dep.destination.terminus,
+                    dep.direction,
+                    now.to_rfc3339(),
+                    dep.time.to_rfc3339(),
+                    dep.realtime as i64,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Fetch all observations for `stop_id` (optionally filtered to one
+    /// `route`) recorded at or after `since`.
+    pub fn history(&self, stop_id: &str, route: Option<&str>, since: DateTime<Tz>) -> rusqlite::Result<Vec<Observation>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT route, destination, direction, observed_at, predicted_time, realtime
+             FROM observations
+             WHERE stop_id = ?1 AND observed_at >= ?2 AND (?3 IS NULL OR route = ?3)
+             ORDER BY observed_at ASC",
+        )?;
+        let since_str = since.to_rfc3339();
+        let rows = stmt.query_map(params![stop_id, since_str, route], |row| {
+            let observed_at: String = row.get(3)?;
+            let predicted_time: String = row.get(4)?;
+            Ok(Observation {
+                stop_id: stop_id.to_owned(),
+                route: row.get(0)?,
+                destination: row.get(1)?,
+                direction: row.get(2)?,
+                observed_at: DateTime::parse_from_rfc3339(&observed_at).unwrap().with_timezone(&since.timezone()),
+                predicted_time: DateTime::parse_from_rfc3339(&predicted_time).unwrap().with_timezone(&since.timezone()),
+                realtime: row.get::<_, i64>(5)? != 0,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Fetch every observation ever recorded for `stop_id`, for summary
+    /// statistics.
+    pub fn all(&self, stop_id: &str) -> rusqlite::Result<Vec<Observation>> {
+        self.history(stop_id, None, DateTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap().with_timezone(&chrono_tz::UTC))
+    }
+}
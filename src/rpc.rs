@@ -0,0 +1,106 @@
+//! JSON-RPC 2.0 over stdio (`kvvliveapi rpc`), so editors, launchers
+//! (rofi/albert plugins), and other processes can embed this tool as a
+//! long-lived child process instead of shelling out to the CLI per
+//! request or standing up an HTTP server.
+//!
+//! Requests and responses are newline-delimited JSON-RPC 2.0 objects on
+//! stdin/stdout. Three methods are supported:
+//!
+//! - `searchStops {"name": "..."}` -> [`Stop`](::Stop)`[]`
+//! - `getDepartures {"stopId": "...", "route": "..."?}` -> [`Departure`](::Departure)`[]`
+//! - `subscribe {"stopId": "...", "route": "..."?, "intervalSeconds": N?}`
+//!   -> acknowledges immediately, then pushes `departures` notifications
+//!   (no `id`) with fresh departures every `intervalSeconds` (default
+//!   20) for as long as the process keeps running.
+
+use std::io::{self, BufRead, Write};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use {departures_by_route, departures_by_stop, search_by_name};
+
+#[derive(Deserialize)]
+struct Request {
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn write_message(out: &mut impl Write, message: &Value) {
+    let _ = writeln!(out, "{}", message);
+    let _ = out.flush();
+}
+
+fn write_response(out: &mut impl Write, id: Option<Value>, result: Result<Value, String>) {
+    let message = match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({"jsonrpc": "2.0", "id": id, "error": {"code": -32000, "message": error}}),
+    };
+    write_message(out, &message);
+}
+
+fn handle(request: &Request) -> Result<Value, String> {
+    match request.method.as_str() {
+        "searchStops" => {
+            let name = request.params.get("name").and_then(Value::as_str).ok_or("missing \"name\" param")?;
+            let stops = search_by_name(name).map_err(|e| e.to_string())?;
+            Ok(json!(stops))
+        }
+        "getDepartures" => {
+            let stop_id = request.params.get("stopId").and_then(Value::as_str).ok_or("missing \"stopId\" param")?;
+            let departures = match request.params.get("route").and_then(Value::as_str) {
+                Some(route) => departures_by_route(stop_id, route),
+                None => departures_by_stop(stop_id),
+            }
+            .map_err(|e| e.to_string())?;
+            Ok(json!(departures.departures))
+        }
+        "subscribe" => {
+            let stop_id = request.params.get("stopId").and_then(Value::as_str).ok_or("missing \"stopId\" param")?.to_owned();
+            let route = request.params.get("route").and_then(Value::as_str).map(|s| s.to_owned());
+            let interval = request.params.get("intervalSeconds").and_then(Value::as_u64).unwrap_or(20);
+            thread::spawn(move || loop {
+                let result = match &route {
+                    Some(route) => departures_by_route(&stop_id, route),
+                    None => departures_by_stop(&stop_id),
+                };
+                if let Ok(departures) = result {
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "departures",
+                        "params": {"stopId": stop_id, "departures": departures.departures},
+                    });
+                    write_message(&mut io::stdout(), &notification);
+                }
+                thread::sleep(Duration::from_secs(interval));
+            });
+            Ok(json!({"subscribed": true}))
+        }
+        other => Err(format!("unknown method \"{}\"", other)),
+    }
+}
+
+/// Run the JSON-RPC loop, reading one request per line from stdin and
+/// writing one response per line to stdout, until stdin closes.
+pub fn run() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                let id = request.id.clone();
+                let result = handle(&request);
+                write_response(&mut stdout, id, result);
+            }
+            Err(e) => write_response(&mut stdout, None, Err(format!("invalid JSON-RPC request: {}", e))),
+        }
+    }
+    Ok(())
+}
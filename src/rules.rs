@@ -0,0 +1,217 @@
+//! A small rules engine for turning boards into alerts: "alert me when a
+//! departure I care about is coming up soon, but only during the hours I
+//! actually care". Meant to be evaluated on every poll in watch/daemon mode
+//! and dispatched to a [`notify::Notifier`](::notify::Notifier).
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Weekday};
+use chrono_tz::Tz;
+
+use {Departure, Departures};
+
+/// Whether `date` is a public holiday in Baden-Württemberg, the region the
+/// crate's hardcoded stops and lines live in. Movable feasts are derived
+/// from the date of Easter Sunday (Gauss's algorithm).
+fn is_public_holiday(date: NaiveDate) -> bool {
+    let year = date.year();
+
+    let fixed = [(1, 1), (1, 6), (5, 1), (10, 3), (11, 1), (12, 25), (12, 26)];
+    if fixed.iter().any(|&(m, d)| date.month() == m && date.day() == d) {
+        return true;
+    }
+
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    let easter = NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap();
+
+    // Good Friday, Easter Monday, Ascension, Whit Monday, Corpus Christi
+    [-2, 1, 39, 50, 60].iter().any(|offset| easter + Duration::days(*offset) == date)
+}
+
+/// A single alert rule: which stop/route/destination to watch, when the
+/// rule is active, and how soon before departure to alert.
+pub struct AlertRule {
+    stop_id: String,
+    route: Option<String>,
+    destination: Option<String>,
+    minutes_before: i64,
+    days: Option<Vec<Weekday>>,
+    time_range: Option<(NaiveTime, NaiveTime)>,
+    skip_holidays: bool,
+}
+
+impl AlertRule {
+    /// Alert on departures from `stop_id` starting `minutes_before` minutes
+    /// ahead of their predicted time.
+    pub fn new(stop_id: &str, minutes_before: i64) -> AlertRule {
+        AlertRule {
+            stop_id: stop_id.to_owned(),
+            route: None,
+            destination: None,
+            minutes_before,
+            days: None,
+            time_range: None,
+            skip_holidays: false,
+        }
+    }
+
+    /// Only alert for this route.
+    pub fn route(mut self, route: &str) -> AlertRule {
+        self.route = Some(route.to_owned());
+        self
+    }
+
+    /// Only alert for departures towards this destination.
+    pub fn destination(mut self, destination: &str) -> AlertRule {
+        self.destination = Some(destination.to_owned());
+        self
+    }
+
+    /// Only alert on these days of the week.
+    pub fn days(mut self, days: Vec<Weekday>) -> AlertRule {
+        self.days = Some(days);
+        self
+    }
+
+    /// Only alert within this time-of-day window (inclusive).
+    pub fn time_range(mut self, from: NaiveTime, to: NaiveTime) -> AlertRule {
+        self.time_range = Some((from, to));
+        self
+    }
+
+    /// Don't alert on Baden-Württemberg public holidays, so a daemon
+    /// doesn't keep polling a commute rule on a day nobody's commuting.
+    pub fn skip_holidays(mut self, skip: bool) -> AlertRule {
+        self.skip_holidays = skip;
+        self
+    }
+
+    /// Whether this rule's schedule (days, time range, holidays) is active
+    /// at `now`. A poller can use this to skip stops nobody cares about
+    /// right now instead of fetching and immediately discarding the board.
+    pub fn is_active(&self, now: DateTime<Tz>) -> bool {
+        let day_ok = self.days.as_ref().is_none_or(|days| days.contains(&now.weekday()));
+        let time_ok = self.time_range.is_none_or(|(from, to)| {
+            let t = now.time();
+            t >= from && t <= to
+        });
+        let holiday_ok = !self.skip_holidays || !is_public_holiday(now.date_naive());
+        day_ok && time_ok && holiday_ok
+    }
+
+    fn matches(&self, departure: &Departure) -> bool {
+        self.route.as_ref().is_none_or(|r| &departure.route == r)
+            && self
+                .destination
+                .as_ref()
+                .is_none_or(|d| departure.destination.terminus.eq_ignore_ascii_case(d))
+    }
+}
+
+/// Evaluate `rule` against `board` (fetched for `stop_id`) at `now`,
+/// returning the departures that should trigger an alert right now.
+pub fn evaluate<'a>(rule: &AlertRule, stop_id: &str, now: DateTime<Tz>, board: &'a Departures) -> Vec<&'a Departure> {
+    if rule.stop_id != stop_id || !rule.is_active(now) {
+        return Vec::new();
+    }
+
+    board
+        .departures
+        .iter()
+        .filter(|d| rule.matches(d))
+        .filter(|d| {
+            let minutes_until = d.time.signed_duration_since(now).num_minutes();
+            minutes_until >= 0 && minutes_until <= rule.minutes_before
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+    use Destination;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Tz> {
+        Berlin.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn fixed_holidays_are_recognized() {
+        assert!(is_public_holiday(at(2026, 1, 1, 12, 0).date_naive()));
+        assert!(is_public_holiday(at(2026, 12, 25, 12, 0).date_naive()));
+    }
+
+    #[test]
+    fn movable_feasts_derived_from_easter_2026_are_recognized() {
+        // Easter Sunday 2026 is April 5th.
+        assert!(is_public_holiday(at(2026, 4, 3, 12, 0).date_naive())); // Good Friday
+        assert!(is_public_holiday(at(2026, 4, 6, 12, 0).date_naive())); // Easter Monday
+        assert!(is_public_holiday(at(2026, 5, 14, 12, 0).date_naive())); // Ascension
+        assert!(is_public_holiday(at(2026, 5, 25, 12, 0).date_naive())); // Whit Monday
+        assert!(is_public_holiday(at(2026, 6, 4, 12, 0).date_naive())); // Corpus Christi
+    }
+
+    #[test]
+    fn an_ordinary_day_is_not_a_holiday() {
+        assert!(!is_public_holiday(at(2026, 8, 10, 12, 0).date_naive()));
+    }
+
+    #[test]
+    fn is_active_respects_skip_holidays() {
+        let rule = AlertRule::new("de:0:0", 10).skip_holidays(true);
+        assert!(!rule.is_active(at(2026, 12, 25, 9, 0)));
+        assert!(rule.is_active(at(2026, 8, 10, 9, 0)));
+    }
+
+    #[test]
+    fn is_active_respects_days_and_time_range() {
+        let rule = AlertRule::new("de:0:0", 10)
+            .days(vec![Weekday::Mon])
+            .time_range(NaiveTime::from_hms_opt(7, 0, 0).unwrap(), NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        // 2026-08-10 is a Monday.
+        assert!(rule.is_active(at(2026, 8, 10, 8, 0)));
+        assert!(!rule.is_active(at(2026, 8, 10, 10, 0)));
+        assert!(!rule.is_active(at(2026, 8, 11, 8, 0)));
+    }
+
+    #[test]
+    fn evaluate_filters_by_stop_route_and_lead_time() {
+        let now = at(2026, 8, 10, 8, 0);
+        let rule = AlertRule::new("de:0:0", 10).route("S2");
+        let board = Departures::new(
+            now,
+            "Test",
+            vec![
+                Departure::new("S2", Destination::new("Rheinstetten", Vec::new(), "Rheinstetten"), "1", now + Duration::minutes(5), false, true, 0),
+                Departure::new("S1", Destination::new("Hochstetten", Vec::new(), "Hochstetten"), "1", now + Duration::minutes(5), false, true, 0),
+                Departure::new("S2", Destination::new("Rheinstetten", Vec::new(), "Rheinstetten"), "1", now + Duration::minutes(20), false, true, 0),
+            ],
+        );
+
+        let matches = evaluate(&rule, "de:0:0", now, &board);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].route, "S2");
+        assert_eq!(matches[0].time, now + Duration::minutes(5));
+    }
+
+    #[test]
+    fn evaluate_is_empty_for_a_different_stop() {
+        let now = at(2026, 8, 10, 8, 0);
+        let rule = AlertRule::new("de:0:0", 10);
+        let board = Departures::new(now, "Test", Vec::new());
+        assert!(evaluate(&rule, "de:9:9", now, &board).is_empty());
+    }
+}
@@ -0,0 +1,288 @@
+//! Schedule-backed queries that go beyond what the live "now" endpoint can
+//! answer, such as asking for departures at an arbitrary future time.
+//!
+//! The live API only ever reports the current board, so answering these
+//! queries needs a second backend (an EFA trip-planner fallback, or a GTFS
+//! schedule). Neither is wired up yet; this module defines the intended
+//! shape of the API so callers can start writing against it, and fails
+//! loudly with [`ScheduleError::Unsupported`] until a backend lands.
+
+use std::error::Error;
+use std::fmt;
+
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Weekday};
+use chrono_tz::Tz;
+
+use Departure;
+use Departures;
+
+/// Hours (24h, local to `now`'s timezone) during which KVV's night network
+/// is the only thing running, the regular daytime lines having already
+/// stopped for the night.
+const NIGHT_SERVICE_HOURS: std::ops::Range<u32> = 1..5;
+
+/// How far a live departure's time may drift from a scheduled one and
+/// still be considered a match for the same trip, rather than a
+/// different, unscheduled one.
+const MATCH_WINDOW_MINUTES: i64 = 20;
+
+/// Errors specific to schedule-backed (as opposed to live) queries.
+#[derive(Debug)]
+pub enum ScheduleError {
+    /// No schedule backend (EFA fallback or GTFS feed) is configured yet.
+    Unsupported(String),
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ScheduleError::Unsupported(ref msg) => write!(f, "schedule query not supported: {}", msg),
+        }
+    }
+}
+
+impl Error for ScheduleError {
+    fn description(&self) -> &str {
+        match *self {
+            ScheduleError::Unsupported(ref msg) => msg,
+        }
+    }
+}
+
+/// Get the departures expected at `stop_id` at a given future (or past)
+/// point in time, rather than "now".
+///
+/// This currently always fails with [`ScheduleError::Unsupported`]: the live
+/// endpoint has no concept of a time window, and no EFA fallback or GTFS
+/// schedule backend is wired up yet. It is kept as a stable entry point so
+/// a future backend can be plugged in without changing call sites.
+pub fn departures_at(stop_id: &str, at: DateTime<Tz>) -> Result<Departures, ScheduleError> {
+    let _ = (stop_id, at);
+    Err(ScheduleError::Unsupported(
+        "no EFA fallback or GTFS schedule backend is configured; only the live \"now\" board is available".to_owned(),
+    ))
+}
+
+/// Whether `now` falls in KVV's night-network window: after the regular
+/// daytime lines have stopped for the night but before they resume, when
+/// only the `NL`-prefixed night lines (see
+/// [`Line::is_night_line`](::linemeta::Line::is_night_line)) and a reduced
+/// weekend schedule are running.
+///
+/// This is a heuristic based on KVV's published weekly pattern (night
+/// lines run the nights before Saturday, Sunday, and public holidays),
+/// not a lookup against an actual calendar: no GTFS feed or EFA fallback
+/// is wired up yet (see the module docs above), so a particular holiday's
+/// night-service schedule can't be accounted for here.
+pub fn is_night_service(now: DateTime<Tz>) -> bool {
+    if !NIGHT_SERVICE_HOURS.contains(&now.hour()) {
+        return false;
+    }
+    // Past-midnight hours on a Saturday or Sunday are the tail end of a
+    // Friday or Saturday night, the two nights KVV's night network runs.
+    matches!(now.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+/// Ask when the last `route` departure in `direction` from `stop_id` runs
+/// on `date`, e.g. to warn "last S2 leaves at 00:42" for evening planning.
+///
+/// Like [`departures_at`], this always fails with [`ScheduleError::Unsupported`]:
+/// the live endpoint's horizon is far too short to see the last departure
+/// of the day, and no EFA fallback or GTFS schedule backend is wired up
+/// yet. Kept as a stable entry point for when one is.
+pub fn last_departure(stop_id: &str, route: &str, direction: &str, date: NaiveDate) -> Result<DateTime<Tz>, ScheduleError> {
+    let _ = (stop_id, route, direction, date);
+    Err(ScheduleError::Unsupported(
+        "no EFA fallback or GTFS schedule backend is configured; the live \"now\" board's horizon is too \
+         short to know the last departure of the day"
+            .to_owned(),
+    ))
+}
+
+/// A line's first and last departure from a stop on a given day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceSpan {
+    pub route: String,
+    pub first: DateTime<Tz>,
+    pub last: DateTime<Tz>,
+}
+
+/// Ask for the first and last departure of each line serving `stop_id` on
+/// `date`, e.g. to check whether an early airport connection exists
+/// before KVV's regular network has started running for the day.
+///
+/// Like [`departures_at`] and [`last_departure`], always fails with
+/// [`ScheduleError::Unsupported`] until a GTFS or EFA fallback backend is
+/// wired up — the live board only ever shows what's coming up next, not a
+/// whole day's span.
+pub fn service_span(stop_id: &str, date: NaiveDate) -> Result<Vec<ServiceSpan>, ScheduleError> {
+    let _ = (stop_id, date);
+    Err(ScheduleError::Unsupported(
+        "no EFA fallback or GTFS schedule backend is configured; the live \"now\" board can't see a \
+         whole day's first and last departures"
+            .to_owned(),
+    ))
+}
+
+/// One ride in a suggested [`Itinerary`]: board `route` at `from`,
+/// departing `departure`, and ride it to `to`, arriving around `arrival`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leg {
+    pub route: String,
+    pub from: String,
+    pub to: String,
+    pub departure: DateTime<Tz>,
+    pub arrival: DateTime<Tz>,
+}
+
+/// A suggested way to get from one stop to another: a single leg, or two
+/// legs with a transfer at an intermediate stop in between.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Itinerary {
+    pub legs: Vec<Leg>,
+}
+
+/// Suggest up to `limit` itineraries with at most one transfer to get
+/// from `from` to `to`, departing at or after `now`.
+///
+/// A direct connection just needs the live boards this crate already
+/// fetches, but finding a transfer point between two arbitrary stops
+/// needs a GTFS stop/route topology (which lines call at which stops,
+/// and in what order) that the live API doesn't expose — so, like the
+/// rest of this module, this always fails with [`ScheduleError::Unsupported`]
+/// until one is wired up. Not intended to become a full multi-modal
+/// router; one optional transfer is the limit.
+pub fn suggest_connections(from: &str, to: &str, now: DateTime<Tz>, limit: usize) -> Result<Vec<Itinerary>, ScheduleError> {
+    let _ = (from, to, now, limit);
+    Err(ScheduleError::Unsupported(
+        "no GTFS stop/route topology is configured; finding a transfer point between two stops needs \
+         one, and only direct live boards (no topology) are available"
+            .to_owned(),
+    ))
+}
+
+/// One stop reachable from an isochrone's origin, with the earliest time
+/// [`isochrone`] found to arrive there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reachable {
+    pub stop_id: String,
+    pub earliest_arrival: DateTime<Tz>,
+}
+
+/// Compute which stops are reachable from `stop_id` within `max_minutes`
+/// of departing at or after `now`, each with its earliest arrival time —
+/// an isochrone, useful for apartment hunting or accessibility studies.
+///
+/// Optionally adjusted by live delays on the first leg out of `stop_id`,
+/// but the bulk of the computation (walking the stop/route graph to see
+/// what's within range) needs the same GTFS topology
+/// [`suggest_connections`] does, and that isn't wired up yet — so this
+/// always fails with [`ScheduleError::Unsupported`] for now.
+pub fn isochrone(stop_id: &str, now: DateTime<Tz>, max_minutes: i64) -> Result<Vec<Reachable>, ScheduleError> {
+    let _ = (stop_id, now, max_minutes);
+    Err(ScheduleError::Unsupported(
+        "no GTFS stop/route topology is configured; computing an isochrone needs one to know which \
+         stops a line reaches and how long each hop takes"
+            .to_owned(),
+    ))
+}
+
+/// One scheduled trip at a stop, as read from a GTFS feed's
+/// `stop_times`/`trips`/`routes` tables.
+///
+/// This crate doesn't parse GTFS feeds itself (that's a sizeable CSV/zip
+/// parsing job better left to a dedicated `gtfs-structures`-style crate);
+/// callers load a feed with whatever GTFS library they prefer and convert
+/// the relevant rows into `ScheduledTrip`s to cross-check against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledTrip {
+    pub route: String,
+    pub destination: String,
+    pub scheduled_time: DateTime<Tz>,
+}
+
+/// The result of matching one live departure against a GTFS schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Deviation {
+    /// Matched a scheduled trip, running on time (within a minute).
+    OnTime,
+    /// Matched a scheduled trip, running this many minutes late (negative
+    /// for early).
+    Delayed(i64),
+    /// Matched a scheduled trip on the same route and time, but terminating
+    /// at a different destination than scheduled — likely short-turned.
+    ShortTurned { scheduled_destination: String },
+    /// No scheduled trip on this route was found within the match window;
+    /// likely an extra, unscheduled service.
+    Extra,
+}
+
+/// Cross-check a live board against a GTFS schedule, pairing each live
+/// departure with the closest scheduled trip on the same route (within
+/// [`MATCH_WINDOW_MINUTES`]) to compute its true deviation from schedule.
+///
+/// Scheduled trips that never show up live (cancellations) are not
+/// reported here, since this only walks the live side of the board.
+pub fn cross_check<'a>(live: &'a Departures, timetable: &[ScheduledTrip]) -> Vec<(&'a Departure, Deviation)> {
+    live.departures
+        .iter()
+        .map(|departure| {
+            let best_match = timetable
+                .iter()
+                .filter(|trip| trip.route == departure.route)
+                .min_by_key(|trip| (trip.scheduled_time - departure.time).num_seconds().abs());
+
+            let deviation = match best_match {
+                Some(trip) if (departure.time - trip.scheduled_time).num_minutes().abs() <= MATCH_WINDOW_MINUTES => {
+                    if trip.destination != departure.destination.terminus {
+                        Deviation::ShortTurned { scheduled_destination: trip.destination.clone() }
+                    } else {
+                        let delay = (departure.time - trip.scheduled_time).num_minutes();
+                        if delay.abs() < 1 {
+                            Deviation::OnTime
+                        } else {
+                            Deviation::Delayed(delay)
+                        }
+                    }
+                }
+                _ => Deviation::Extra,
+            };
+            (departure, deviation)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use chrono_tz::Europe::Berlin;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Tz> {
+        Berlin.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn night_service_runs_early_saturday_morning() {
+        // 2026-08-08 is a Saturday.
+        assert!(is_night_service(at(2026, 8, 8, 2, 30)));
+    }
+
+    #[test]
+    fn night_service_runs_early_sunday_morning() {
+        // 2026-08-09 is a Sunday.
+        assert!(is_night_service(at(2026, 8, 9, 3, 0)));
+    }
+
+    #[test]
+    fn no_night_service_on_a_weekday_night() {
+        // 2026-08-11 is a Tuesday.
+        assert!(!is_night_service(at(2026, 8, 11, 2, 30)));
+    }
+
+    #[test]
+    fn no_night_service_outside_the_night_hours() {
+        // Still a Saturday, but well into the daytime service window.
+        assert!(!is_night_service(at(2026, 8, 8, 12, 0)));
+    }
+}
@@ -0,0 +1,126 @@
+//! Spreading periodic refreshes for a configured set of stops evenly
+//! across the poll interval, instead of firing them all back-to-back
+//! every tick — a [`daemon`](::daemon) or exporter watching 50 stops
+//! that did that would burst 50 requests into the same second, every
+//! interval, which looks like abuse to the upstream API.
+//!
+//! Priority isn't just a tie-breaker: a stop's effective refresh
+//! interval is `interval / priority`, so a foreground stop (one
+//! currently on screen) can be polled several times as often as a
+//! background favorite without a second scheduler or poll loop.
+
+use std::time::{Duration, Instant};
+
+/// Priority for a stop not currently shown anywhere; the default.
+pub const BACKGROUND_PRIORITY: u32 = 1;
+
+/// Priority for a stop currently displayed, e.g. the board a TUI user
+/// has open — polled several times as often as a background favorite.
+pub const FOREGROUND_PRIORITY: u32 = 4;
+
+struct Slot {
+    stop_id: String,
+    priority: u32,
+    due: Instant,
+    /// Set by [`PrefetchScheduler::bump_priority`]: the priority to
+    /// restore, and when, once the bump expires.
+    revert: Option<(u32, Instant)>,
+}
+
+/// A pull-based scheduler: a poll loop repeatedly calls [`next_due`]
+/// (sleeping [`time_until_next`] in between) instead of looping over all
+/// configured stops on every tick.
+///
+/// [`next_due`]: PrefetchScheduler::next_due
+/// [`time_until_next`]: PrefetchScheduler::time_until_next
+pub struct PrefetchScheduler {
+    interval: Duration,
+    slots: Vec<Slot>,
+}
+
+impl PrefetchScheduler {
+    /// A scheduler refreshing every stop in `stops` (stop ID and
+    /// priority pairs — see [`BACKGROUND_PRIORITY`]/[`FOREGROUND_PRIORITY`])
+    /// once per `interval / priority` on average, with their first
+    /// refreshes staggered evenly across the interval rather than all
+    /// due immediately.
+    pub fn new(stops: Vec<(String, u32)>, interval: Duration) -> PrefetchScheduler {
+        let now = Instant::now();
+        let spacing = interval / stops.len().max(1) as u32;
+        let slots = stops
+            .into_iter()
+            .enumerate()
+            .map(|(i, (stop_id, priority))| Slot { stop_id, priority, due: now + spacing * i as u32, revert: None })
+            .collect();
+        PrefetchScheduler { interval, slots }
+    }
+
+    fn expire_bumps(&mut self) {
+        let now = Instant::now();
+        for slot in &mut self.slots {
+            if let Some((original, expires_at)) = slot.revert {
+                if now >= expires_at {
+                    slot.priority = original;
+                    slot.revert = None;
+                }
+            }
+        }
+    }
+
+    /// The highest-priority stop that's currently due for a refresh, if
+    /// any, immediately rescheduling it `interval / priority` from now.
+    /// Ties are broken by priority, highest first.
+    pub fn next_due(&mut self) -> Option<String> {
+        self.expire_bumps();
+        let now = Instant::now();
+        let (idx, _) = self.slots.iter().enumerate().filter(|(_, s)| s.due <= now).max_by_key(|(_, s)| s.priority)?;
+        let stop_id = self.slots[idx].stop_id.clone();
+        let priority = self.slots[idx].priority;
+        self.slots[idx].due = now + self.interval / priority.max(1);
+        Some(stop_id)
+    }
+
+    /// How long until the next stop becomes due, for a poll loop to
+    /// sleep on between [`next_due`](PrefetchScheduler::next_due) calls
+    /// instead of busy-waiting.
+    pub fn time_until_next(&self) -> Duration {
+        let now = Instant::now();
+        self.slots.iter().map(|s| s.due.saturating_duration_since(now)).min().unwrap_or(self.interval)
+    }
+
+    /// Change a stop's priority permanently, e.g. because the user
+    /// pinned it as a favorite. Takes effect starting with its next
+    /// poll. For a temporary change, use
+    /// [`bump_priority`](PrefetchScheduler::bump_priority) instead.
+    pub fn set_priority(&mut self, stop_id: &str, priority: u32) {
+        if let Some(slot) = self.slots.iter_mut().find(|s| s.stop_id == stop_id) {
+            slot.priority = priority;
+            slot.revert = None;
+        }
+    }
+
+    /// Temporarily raise (or lower) a stop's priority, automatically
+    /// reverting to its priority from before the bump once `for_duration`
+    /// elapses — e.g. a TUI bumping the currently viewed stop to
+    /// [`FOREGROUND_PRIORITY`] while it's on screen, without having to
+    /// remember to set it back when the user navigates away.
+    pub fn bump_priority(&mut self, stop_id: &str, priority: u32, for_duration: Duration) {
+        if let Some(slot) = self.slots.iter_mut().find(|s| s.stop_id == stop_id) {
+            let original = slot.revert.map_or(slot.priority, |(original, _)| original);
+            slot.priority = priority;
+            slot.revert = Some((original, Instant::now() + for_duration));
+        }
+    }
+
+    /// Start scheduling an additional stop, due immediately.
+    pub fn add_stop(&mut self, stop_id: String, priority: u32) {
+        if !self.slots.iter().any(|s| s.stop_id == stop_id) {
+            self.slots.push(Slot { stop_id, priority, due: Instant::now(), revert: None });
+        }
+    }
+
+    /// Stop scheduling a stop.
+    pub fn remove_stop(&mut self, stop_id: &str) {
+        self.slots.retain(|s| s.stop_id != stop_id);
+    }
+}
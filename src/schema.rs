@@ -0,0 +1,140 @@
+//! Detects when a live response's shape has drifted from what this
+//! crate's types expect: fields the model doesn't know about, fields the
+//! model expects that are missing, and values (e.g. [`Departure::traction`])
+//! outside the set this crate has ever observed. Meant to run against
+//! [`KvvClient::get_raw`](::client::KvvClient::get_raw) in downstream CI or
+//! periodically in a long-running daemon, so an upstream format change is
+//! noticed before it silently degrades or breaks a board.
+
+use serde_json::Value;
+
+const BOARD_FIELDS: &[&str] = &["timestamp", "stopName", "departures"];
+const DEPARTURE_FIELDS: &[&str] = &["route", "destination", "direction", "time", "lowfloor", "realtime", "traction"];
+
+/// Fields this crate understands but doesn't expect on every departure —
+/// `platform` isn't reported by every EFA deployment or every stop, so
+/// its absence isn't schema drift, just a stop that doesn't have one.
+const DEPARTURE_OPTIONAL_FIELDS: &[&str] = &["platform", "occupancy"];
+
+/// Values [`Departure::traction`](::Departure::traction) has been observed
+/// to take. Not documented by the upstream API; just what this crate's
+/// maintainers have seen in the wild.
+const KNOWN_TRACTION_VALUES: &[u64] = &[0, 2];
+
+/// One discrepancy between a live response and what this crate expects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Drift {
+    /// A field the response has that no version of this crate's model
+    /// understands yet.
+    UnknownField { path: String, field: String },
+    /// A field this crate's model expects that the response doesn't have.
+    MissingField { path: String, field: String },
+    /// A field that is present, but holds a value outside the set this
+    /// crate has ever observed for it.
+    UnknownValue { path: String, field: String, value: String },
+}
+
+/// `required_fields` must be present or a [`Drift::MissingField`] is
+/// reported; `optional_fields` are merely known, so their absence is not
+/// drift but their presence doesn't count as [`Drift::UnknownField`]
+/// either.
+fn check_object(path: &str, value: &Value, required_fields: &[&str], optional_fields: &[&str]) -> Vec<Drift> {
+    let obj = match value.as_object() {
+        Some(obj) => obj,
+        None => return Vec::new(),
+    };
+
+    let mut drifts: Vec<Drift> = obj
+        .keys()
+        .filter(|field| !required_fields.contains(&field.as_str()) && !optional_fields.contains(&field.as_str()))
+        .map(|field| Drift::UnknownField { path: path.to_owned(), field: field.clone() })
+        .collect();
+
+    drifts.extend(
+        required_fields
+            .iter()
+            .filter(|field| !obj.contains_key(**field))
+            .map(|field| Drift::MissingField { path: path.to_owned(), field: (**field).to_owned() }),
+    );
+
+    drifts
+}
+
+/// Compare a raw departures-board response against this crate's known
+/// schema. Takes the [`serde_json::Value`] from
+/// [`KvvClient::get_raw`](::client::KvvClient::get_raw) rather than
+/// fetching it itself, so it composes with whatever endpoint the caller
+/// used.
+pub fn check_departures(value: &Value) -> Vec<Drift> {
+    let mut drifts = check_object("board", value, BOARD_FIELDS, &[]);
+
+    if let Some(departures) = value.get("departures").and_then(Value::as_array) {
+        for (i, departure) in departures.iter().enumerate() {
+            let path = format!("departures[{}]", i);
+            drifts.extend(check_object(&path, departure, DEPARTURE_FIELDS, DEPARTURE_OPTIONAL_FIELDS));
+
+            if let Some(traction) = departure.get("traction").and_then(Value::as_u64) {
+                if !KNOWN_TRACTION_VALUES.contains(&traction) {
+                    drifts.push(Drift::UnknownValue { path, field: "traction".to_owned(), value: traction.to_string() });
+                }
+            }
+        }
+    }
+
+    drifts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn good_departure() -> Value {
+        json!({"route": "S2", "destination": "Rheinstetten", "direction": "1", "time": "5", "lowfloor": true, "realtime": true, "traction": 0})
+    }
+
+    #[test]
+    fn matching_board_has_no_drift() {
+        let value = json!({"timestamp": "now", "stopName": "Test", "departures": [good_departure()]});
+        assert_eq!(check_departures(&value), vec![]);
+    }
+
+    #[test]
+    fn known_optional_field_absent_is_not_drift() {
+        let value = json!({"timestamp": "now", "stopName": "Test", "departures": [good_departure()]});
+        assert_eq!(check_departures(&value), vec![]);
+    }
+
+    #[test]
+    fn unknown_field_is_reported() {
+        let mut departure = good_departure();
+        departure.as_object_mut().unwrap().insert("newField".to_owned(), json!("surprise"));
+        let value = json!({"timestamp": "now", "stopName": "Test", "departures": [departure]});
+        assert_eq!(
+            check_departures(&value),
+            vec![Drift::UnknownField { path: "departures[0]".to_owned(), field: "newField".to_owned() }]
+        );
+    }
+
+    #[test]
+    fn missing_required_field_is_reported() {
+        let mut departure = good_departure();
+        departure.as_object_mut().unwrap().remove("traction");
+        let value = json!({"timestamp": "now", "stopName": "Test", "departures": [departure]});
+        assert_eq!(
+            check_departures(&value),
+            vec![Drift::MissingField { path: "departures[0]".to_owned(), field: "traction".to_owned() }]
+        );
+    }
+
+    #[test]
+    fn unknown_traction_value_is_reported() {
+        let mut departure = good_departure();
+        departure.as_object_mut().unwrap().insert("traction".to_owned(), json!(9));
+        let value = json!({"timestamp": "now", "stopName": "Test", "departures": [departure]});
+        assert_eq!(
+            check_departures(&value),
+            vec![Drift::UnknownValue { path: "departures[0]".to_owned(), field: "traction".to_owned(), value: "9".to_owned() }]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! A minimal REST server mode (`kvvliveapi server`), behind the `server`
+//! feature: a handful of read-only endpoints over this crate's own
+//! [`search_by_name`](::search_by_name) and
+//! [`departures_by_stop`](::departures_by_stop), for third parties
+//! building on a hosted proxy instance instead of embedding this crate
+//! directly. Serves its own [`openapi_document`] at `/openapi.json` so
+//! those third parties get client generation and a stable contract, and
+//! every error response is the same typed JSON shape rather than bare
+//! text.
+//!
+//! Deliberately built on the same lightweight, synchronous `tiny_http`
+//! server as `kvv-mock-server` rather than pulling in an async web
+//! framework — this crate has no async runtime anywhere else, and one
+//! handful of routes doesn't need one.
+
+use std::io::Cursor;
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use url::Url;
+
+use {departures_by_stop, search_by_name};
+
+/// One error response shape for every failure this server returns, so
+/// clients can rely on `{"error": {"code": ..., "message": ...}}`
+/// regardless of which endpoint or failure mode produced it.
+fn error_body(code: &str, message: &str) -> Value {
+    json!({"error": {"code": code, "message": message}})
+}
+
+fn json_response(status: u16, body: Value) -> Response<Cursor<Vec<u8>>> {
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).expect("static header is valid");
+    Response::from_string(body.to_string()).with_status_code(status).with_header(header)
+}
+
+/// The OpenAPI 3.0 document describing this server's endpoints, served
+/// at `GET /openapi.json`. Hand-written rather than generated from the
+/// route handlers below, so it stays the single source of truth for
+/// what's actually a stable, supported contract — an internal helper
+/// route is free to exist without being promised to third parties.
+pub fn openapi_document() -> Value {
+    let stop = json!({
+        "type": "object",
+        "required": ["name", "id", "lat", "lon"],
+        "properties": {
+            "name": {"type": "string"},
+            "id": {"type": "string"},
+            "lat": {"type": "number", "format": "double"},
+            "lon": {"type": "number", "format": "double"},
+        },
+    });
+    let destination = json!({
+        "type": "object",
+        "required": ["terminus", "via", "raw"],
+        "properties": {
+            "terminus": {"type": "string"},
+            "via": {"type": "array", "items": {"type": "string"}},
+            "raw": {"type": "string"},
+        },
+    });
+    let departure = json!({
+        "type": "object",
+        "required": ["route", "destination", "direction", "time", "lowfloor", "realtime", "traction"],
+        "properties": {
+            "route": {"type": "string"},
+            "destination": {"$ref": "#/components/schemas/Destination"},
+            "direction": {"type": "string"},
+            "time": {"type": "string", "format": "date-time"},
+            "lowfloor": {"type": "boolean"},
+            "realtime": {"type": "boolean"},
+            "traction": {"type": "integer", "format": "int32"},
+        },
+    });
+    let error = json!({
+        "type": "object",
+        "required": ["error"],
+        "properties": {
+            "error": {
+                "type": "object",
+                "required": ["code", "message"],
+                "properties": {
+                    "code": {"type": "string"},
+                    "message": {"type": "string"},
+                },
+            },
+        },
+    });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {"title": "kvvliveapi", "version": env!("CARGO_PKG_VERSION")},
+        "paths": {
+            "/stops": {
+                "get": {
+                    "summary": "Search for stops by (partial) name",
+                    "parameters": [{
+                        "name": "name",
+                        "in": "query",
+                        "required": true,
+                        "schema": {"type": "string"},
+                    }],
+                    "responses": {
+                        "200": {
+                            "description": "Matching stops",
+                            "content": {"application/json": {"schema": {"type": "array", "items": {"$ref": "#/components/schemas/Stop"}}}},
+                        },
+                        "400": {
+                            "description": "Missing or invalid query parameters",
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Error"}}},
+                        },
+                    },
+                },
+            },
+            "/departures/{stopId}": {
+                "get": {
+                    "summary": "Fetch the upcoming departures for a stop",
+                    "parameters": [{
+                        "name": "stopId",
+                        "in": "path",
+                        "required": true,
+                        "schema": {"type": "string"},
+                    }],
+                    "responses": {
+                        "200": {
+                            "description": "Upcoming departures",
+                            "content": {"application/json": {"schema": {"type": "array", "items": {"$ref": "#/components/schemas/Departure"}}}},
+                        },
+                        "502": {
+                            "description": "The upstream API request failed",
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Error"}}},
+                        },
+                    },
+                },
+            },
+        },
+        "components": {
+            "schemas": {
+                "Stop": stop,
+                "Destination": destination,
+                "Departure": departure,
+                "Error": error,
+            },
+        },
+    })
+}
+
+fn query_param(url: &str, name: &str) -> Option<String> {
+    let parsed = Url::parse(&format!("http://localhost{}", url)).ok()?;
+    parsed.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned())
+}
+
+fn path_segments(url: &str) -> Vec<String> {
+    let path = url.split('?').next().unwrap_or(url);
+    path.split('/').filter(|s| !s.is_empty()).map(|s| s.to_owned()).collect()
+}
+
+fn handle(request: &Request) -> Response<Cursor<Vec<u8>>> {
+    if request.method() != &Method::Get {
+        return json_response(405, error_body("method_not_allowed", "only GET is supported"));
+    }
+
+    let url = request.url().to_owned();
+    let segments = path_segments(&url);
+
+    match segments.as_slice() {
+        [only] if only == "openapi.json" => json_response(200, openapi_document()),
+        [only] if only == "stops" => match query_param(&url, "name") {
+            None => json_response(400, error_body("missing_parameter", "missing required query parameter \"name\"")),
+            Some(name) => match search_by_name(&name) {
+                Ok(stops) => json_response(200, json!(stops)),
+                Err(e) => json_response(502, error_body("upstream_error", &e.to_string())),
+            },
+        },
+        [first, stop_id] if first == "departures" => match departures_by_stop(stop_id) {
+            Ok(departures) => json_response(200, json!(departures.departures)),
+            Err(e) => json_response(502, error_body("upstream_error", &e.to_string())),
+        },
+        _ => json_response(404, error_body("not_found", "no such route")),
+    }
+}
+
+/// Run the server, blocking forever (one thread per request).
+pub fn run(port: u16) -> std::io::Result<()> {
+    let server = Server::http(("0.0.0.0", port)).map_err(std::io::Error::other)?;
+    println!("kvvliveapi server listening on 0.0.0.0:{} (see /openapi.json)", port);
+    for request in server.incoming_requests() {
+        let response = handle(&request);
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
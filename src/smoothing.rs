@@ -0,0 +1,88 @@
+//! Smoothing a sequence of polled [`Departures`] boards so that displayed
+//! countdowns don't visibly jump backwards (e.g. 4 min → 6 min → 3 min)
+//! from poll-to-poll prediction jitter, the way a public platform display
+//! wouldn't.
+//!
+//! Real delays do happen and must still show up eventually — this isn't a
+//! filter that hides them, just a hysteresis layer that waits for a
+//! later time to be confirmed by a couple of consecutive polls before
+//! trusting it over an earlier one already on screen.
+
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono_tz::Tz;
+
+use {Departure, Departures};
+
+/// How many consecutive polls must agree on a later time before it
+/// replaces an earlier one already being displayed.
+const CONFIRMATIONS_REQUIRED: u32 = 2;
+
+fn key(dep: &Departure) -> (String, String, String) {
+    (dep.route.clone(), dep.destination.terminus.clone(), dep.direction.clone())
+}
+
+struct Entry {
+    displayed_time: DateTime<Tz>,
+    pending_time: Option<DateTime<Tz>>,
+    pending_confirmations: u32,
+}
+
+/// Per-departure smoothing state, carried across polls of the same stop.
+///
+/// Create one `Smoother` per board being watched and feed it every poll
+/// through [`smooth`](Smoother::smooth); a fresh board each time (as
+/// `diff::diff_boards` expects) would defeat the point.
+#[derive(Default)]
+pub struct Smoother {
+    entries: HashMap<(String, String, String), Entry>,
+}
+
+impl Smoother {
+    pub fn new() -> Smoother {
+        Smoother::default()
+    }
+
+    /// Smooth `board`, returning a copy where each departure's time has
+    /// been replaced by its smoothed (hysteresis-delayed) value.
+    pub fn smooth(&mut self, board: &Departures) -> Departures {
+        let departures = board
+            .departures
+            .iter()
+            .map(|dep| {
+                let mut smoothed = dep.clone();
+                smoothed.time = self.smooth_one(dep);
+                smoothed
+            })
+            .collect();
+
+        Departures { timestamp: board.timestamp, stop_name: board.stop_name.clone(), departures }
+    }
+
+    fn smooth_one(&mut self, dep: &Departure) -> DateTime<Tz> {
+        let entry = self.entries.entry(key(dep)).or_insert_with(|| Entry {
+            displayed_time: dep.time,
+            pending_time: None,
+            pending_confirmations: 0,
+        });
+
+        if dep.time <= entry.displayed_time {
+            entry.displayed_time = dep.time;
+            entry.pending_time = None;
+            entry.pending_confirmations = 0;
+        } else if entry.pending_time == Some(dep.time) {
+            entry.pending_confirmations += 1;
+            if entry.pending_confirmations >= CONFIRMATIONS_REQUIRED {
+                entry.displayed_time = dep.time;
+                entry.pending_time = None;
+                entry.pending_confirmations = 0;
+            }
+        } else {
+            entry.pending_time = Some(dep.time);
+            entry.pending_confirmations = 1;
+        }
+
+        entry.displayed_time
+    }
+}
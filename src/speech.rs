@@ -0,0 +1,124 @@
+//! Plain-sentence formatting for screen readers and text-to-speech, as an
+//! alternative to the table-like [`Display`](std::fmt::Display) output.
+
+use chrono::Local;
+
+use clockskew;
+use locale::Lang;
+use {Departure, Departures};
+
+const SMALL_NUMBERS_EN: &[&str] = &[
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen",
+    "nineteen", "twenty",
+];
+
+const SMALL_NUMBERS_DE: &[&str] = &[
+    "null", "eine", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun", "zehn",
+    "elf", "zwölf", "dreizehn", "vierzehn", "fünfzehn", "sechzehn", "siebzehn", "achtzehn",
+    "neunzehn", "zwanzig",
+];
+
+fn spell_out(n: i64, lang: Lang) -> String {
+    let table = match lang {
+        Lang::English => SMALL_NUMBERS_EN,
+        Lang::German => SMALL_NUMBERS_DE,
+    };
+    if n >= 0 && (n as usize) < table.len() {
+        table[n as usize].to_owned()
+    } else {
+        n.to_string()
+    }
+}
+
+/// Render a departure as a full sentence with no tables or symbols, e.g.
+/// "Line S2 toward Spöck departs in four minutes, realtime data."
+pub fn plain_speech(dep: &Departure) -> String {
+    plain_speech_lang(dep, Lang::English)
+}
+
+/// Like [`plain_speech`], but in the given output language.
+pub fn plain_speech_lang(dep: &Departure, lang: Lang) -> String {
+    let now = clockskew::correct(Local::now().with_timezone(&dep.time.timezone()));
+    let minutes = dep.time.signed_duration_since(now).num_minutes();
+
+    match lang {
+        Lang::English => {
+            let timing = if minutes <= 0 {
+                "departs now".to_owned()
+            } else if minutes == 1 {
+                "departs in one minute".to_owned()
+            } else {
+                format!("departs in {} minutes", spell_out(minutes, lang))
+            };
+            let quality = if dep.realtime { "realtime data" } else { "scheduled time, no realtime data" };
+            format!("Line {} toward {} {}, {}.", dep.route, dep.destination.terminus, timing, quality)
+        }
+        Lang::German => {
+            let timing = if minutes <= 0 {
+                "fährt jetzt".to_owned()
+            } else if minutes == 1 {
+                "fährt in einer Minute".to_owned()
+            } else {
+                format!("fährt in {} Minuten", spell_out(minutes, lang))
+            };
+            let quality = if dep.realtime { "Echtzeitdaten" } else { "Fahrplanzeit, keine Echtzeitdaten" };
+            format!("Linie {} Richtung {} {}, {}.", dep.route, dep.destination.terminus, timing, quality)
+        }
+    }
+}
+
+/// One sentence per (route, destination) pair on the board: the next
+/// departure's countdown, followed by any later ones on the same line as
+/// clock times, e.g. "The next S2 toward Spöck departs in four minutes,
+/// then at 22:40."
+///
+/// Suitable for voice assistants and TTS announcements, where a full
+/// per-departure table (as [`plain_speech_lang`] produces) would be too
+/// long to read aloud.
+pub fn summarize(board: &Departures, lang: Lang) -> String {
+    let mut groups: Vec<(&str, &str, Vec<&Departure>)> = Vec::new();
+    for dep in &board.departures {
+        match groups.iter_mut().find(|(route, dest, _)| *route == dep.route && *dest == dep.destination.terminus) {
+            Some((_, _, deps)) => deps.push(dep),
+            None => groups.push((&dep.route, &dep.destination.terminus, vec![dep])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(route, destination, mut deps)| {
+            deps.sort_by_key(|d| d.time);
+            let next = deps[0];
+            let now = clockskew::correct(Local::now().with_timezone(&next.time.timezone()));
+            let minutes = next.time.signed_duration_since(now).num_minutes();
+            let later: Vec<String> = deps[1..].iter().map(|d| d.time.format("%H:%M").to_string()).collect();
+
+            match lang {
+                Lang::English => {
+                    let timing = if minutes <= 0 {
+                        "departs now".to_owned()
+                    } else if minutes == 1 {
+                        "departs in one minute".to_owned()
+                    } else {
+                        format!("departs in {} minutes", spell_out(minutes, lang))
+                    };
+                    let then = if later.is_empty() { String::new() } else { format!(", then at {}", later.join(", ")) };
+                    format!("The next {} toward {} {}{}.", route, destination, timing, then)
+                }
+                Lang::German => {
+                    let timing = if minutes <= 0 {
+                        "fährt jetzt".to_owned()
+                    } else if minutes == 1 {
+                        "fährt in einer Minute".to_owned()
+                    } else {
+                        format!("fährt in {} Minuten", spell_out(minutes, lang))
+                    };
+                    let then = if later.is_empty() { String::new() } else { format!(", danach um {}", later.join(", ")) };
+                    format!("Die nächste {} nach {} {}{}.", route, destination, timing, then)
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,56 @@
+//! Generating OpenStreetMap static-map image URLs centered on a stop or
+//! a set of stops, for callers (the CLI's `stop info --image`, or a future
+//! chat bot) that want a quick visual without embedding a full map widget.
+//!
+//! Uses the public [staticmap.openstreetmap.de](https://staticmap.openstreetmap.de)
+//! service, which renders a PNG from query parameters alone — no API key
+//! needed, but also no uptime guarantee, so treat [`download`] failures
+//! as "no image available" rather than a hard error.
+
+use Stop;
+
+const STATICMAP_BASE: &str = "https://staticmap.openstreetmap.de/staticmap.php";
+
+/// A labeled point to mark on a static map.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+/// Build a static-map URL centered on `markers`' midpoint, pinning each
+/// one.
+///
+/// `zoom` follows the usual OSM convention (0 = whole world, higher
+/// numbers zoom in further); 15 is a reasonable default for a single
+/// stop, lower for a handful of stops spread over a neighbourhood.
+pub fn url_for_markers(markers: &[Marker], zoom: u8, width: u16, height: u16) -> Option<String> {
+    if markers.is_empty() {
+        return None;
+    }
+    let center_lat = markers.iter().map(|m| m.lat).sum::<f64>() / markers.len() as f64;
+    let center_lon = markers.iter().map(|m| m.lon).sum::<f64>() / markers.len() as f64;
+
+    let mut url = format!(
+        "{}?center={:.6},{:.6}&zoom={}&size={}x{}",
+        STATICMAP_BASE, center_lat, center_lon, zoom, width, height
+    );
+    for marker in markers {
+        url.push_str(&format!("&markers={:.6},{:.6},red-pushpin", marker.lat, marker.lon));
+    }
+    Some(url)
+}
+
+/// Build a static-map URL for a single stop.
+pub fn url_for_stop(stop: &Stop, zoom: u8, width: u16, height: u16) -> String {
+    url_for_markers(&[Marker { lat: stop.lat, lon: stop.lon }], zoom, width, height)
+        .expect("a single-element marker list is never empty")
+}
+
+/// Download the image at a static-map URL as raw bytes (PNG).
+pub fn download(url: &str) -> Result<Vec<u8>, reqwest::Error> {
+    let mut response = reqwest::get(url)?;
+    let mut buf = Vec::new();
+    response.copy_to(&mut buf)?;
+    Ok(buf)
+}
@@ -0,0 +1,58 @@
+//! Summary statistics over recorded observations, rendered as simple
+//! unicode bar charts for terminal display.
+
+use std::collections::BTreeMap;
+
+use chrono::Timelike;
+
+use recorder::Observation;
+
+const BAR_CHARS: &[char] = &[' ', '\u{258f}', '\u{258e}', '\u{258d}', '\u{258c}', '\u{258b}', '\u{258a}', '\u{2589}', '\u{2588}'];
+
+/// Summary statistics for a set of observations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub total_observations: usize,
+    /// fraction (0.0..=1.0) of observations that had realtime data
+    pub realtime_coverage: f64,
+    /// number of observations per route, most frequent first
+    pub observations_per_route: Vec<(String, usize)>,
+    /// number of observations per hour of day (0..24), recorded (observed) time
+    pub observations_per_hour: [usize; 24],
+}
+
+/// Summarize a slice of observations.
+pub fn summarize(observations: &[Observation]) -> Summary {
+    let total_observations = observations.len();
+    let realtime_count = observations.iter().filter(|o| o.realtime).count();
+    let realtime_coverage = if total_observations == 0 {
+        0.0
+    } else {
+        realtime_count as f64 / total_observations as f64
+    };
+
+    let mut per_route: BTreeMap<String, usize> = BTreeMap::new();
+    let mut per_hour = [0usize; 24];
+    for obs in observations {
+        *per_route.entry(obs.route.clone()).or_insert(0) += 1;
+        per_hour[obs.observed_at.hour() as usize] += 1;
+    }
+
+    let mut observations_per_route: Vec<(String, usize)> = per_route.into_iter().collect();
+    observations_per_route.sort_by_key(|b| std::cmp::Reverse(b.1));
+
+    Summary { total_observations, realtime_coverage, observations_per_route, observations_per_hour: per_hour }
+}
+
+/// Render a row of counts as a single line of unicode block characters, one
+/// per count, scaled so the largest count uses a full block.
+pub fn bar_chart(counts: &[usize]) -> String {
+    let max = counts.iter().cloned().max().unwrap_or(0).max(1);
+    counts
+        .iter()
+        .map(|&c| {
+            let level = c * (BAR_CHARS.len() - 1) / max;
+            BAR_CHARS[level.min(BAR_CHARS.len() - 1)]
+        })
+        .collect()
+}
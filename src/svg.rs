@@ -0,0 +1,83 @@
+//! Rendering a departure board as SVG: a colored line badge per route (see
+//! [`linemeta`](::linemeta)), a countdown, a stop header, and the data
+//! timestamp. Plain text and vector shapes, so it's equally at home served
+//! to a kiosk browser or displayed on an e-ink frame.
+
+use std::fmt::Write as _;
+
+use linemeta::Line;
+use Departures;
+
+const ROW_HEIGHT: u32 = 40;
+const WIDTH: u32 = 480;
+const BADGE_WIDTH: u32 = 50;
+const DEFAULT_LINE_COLOR: &str = "#555555";
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Render a departure board as a self-contained SVG document.
+pub fn render(board: &Departures) -> String {
+    let height = ROW_HEIGHT * (board.departures.len() as u32 + 1);
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif">"##,
+        WIDTH, height
+    )
+    .unwrap();
+    writeln!(svg, r##"<rect width="{}" height="{}" fill="#ffffff"/>"##, WIDTH, height).unwrap();
+
+    writeln!(
+        svg,
+        r##"<text x="8" y="{}" font-size="16" font-weight="bold">{}</text>"##,
+        ROW_HEIGHT / 2 + 6,
+        escape(&board.stop_name)
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        r##"<text x="{}" y="{}" font-size="12" fill="#888888" text-anchor="end">as of {}</text>"##,
+        WIDTH - 8,
+        ROW_HEIGHT / 2 + 4,
+        board.timestamp.format("%H:%M")
+    )
+    .unwrap();
+
+    for (i, departure) in board.departures.iter().enumerate() {
+        let y = ROW_HEIGHT * (i as u32 + 1);
+        let color = Line::new(departure.route.clone()).metadata().map(|m| m.color).unwrap_or(DEFAULT_LINE_COLOR);
+
+        writeln!(svg, r##"<rect x="8" y="{}" width="{}" height="{}" rx="4" fill="{}"/>"##, y + 6, BADGE_WIDTH, ROW_HEIGHT - 12, color).unwrap();
+        writeln!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="16" fill="#ffffff" text-anchor="middle">{}</text>"##,
+            8 + BADGE_WIDTH / 2,
+            y + ROW_HEIGHT / 2 + 5,
+            escape(&departure.route)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="15">{}</text>"##,
+            8 + BADGE_WIDTH + 12,
+            y + ROW_HEIGHT / 2 + 5,
+            escape(&departure.destination.terminus)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="15" text-anchor="end">{}{}</text>"##,
+            WIDTH - 8,
+            y + ROW_HEIGHT / 2 + 5,
+            departure.time.format("%H:%M"),
+            if departure.realtime { "*" } else { "" },
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,10 @@
+//! Terminal capability queries that formatting code can use instead of
+//! assuming a particular width or a Unix-style console.
+
+use terminal_size::{terminal_size, Width};
+
+/// The width of the controlling terminal in columns, if any is attached
+/// (e.g. not when output is redirected to a file or pipe).
+pub fn width() -> Option<u16> {
+    terminal_size().map(|(Width(w), _)| w)
+}
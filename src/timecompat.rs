@@ -0,0 +1,32 @@
+//! Optional conversion to `time::OffsetDateTime`, for downstream crates
+//! that have standardized on the `time` crate instead of `chrono`. The
+//! internal representation stays `chrono::DateTime<chrono_tz::Tz>`
+//! everywhere else in this crate; these are conversions at the API
+//! boundary only, behind the `time-compat` feature.
+
+use chrono::Offset;
+
+use {Departure, Departures};
+
+fn to_offset_datetime(dt: chrono::DateTime<chrono_tz::Tz>) -> time::OffsetDateTime {
+    let offset_seconds = dt.offset().fix().local_minus_utc();
+    let utc = time::OffsetDateTime::from_unix_timestamp(dt.timestamp())
+        .expect("chrono timestamps fit in time's supported range")
+        .replace_nanosecond(dt.timestamp_subsec_nanos())
+        .expect("chrono's subsecond nanoseconds fit in time's range");
+    utc.to_offset(time::UtcOffset::from_whole_seconds(offset_seconds).expect("chrono's UTC offsets fit in time's range"))
+}
+
+impl Departure {
+    /// This departure's time as a `time::OffsetDateTime`.
+    pub fn time_as_offset_datetime(&self) -> time::OffsetDateTime {
+        to_offset_datetime(self.time)
+    }
+}
+
+impl Departures {
+    /// This board's response timestamp as a `time::OffsetDateTime`.
+    pub fn timestamp_as_offset_datetime(&self) -> time::OffsetDateTime {
+        to_offset_datetime(self.timestamp)
+    }
+}
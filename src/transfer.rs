@@ -0,0 +1,61 @@
+//! Estimating whether a transfer between two lines still holds: given the
+//! next departure on the first leg and the time spent riding it, is there
+//! still a connecting departure at the second stop with enough buffer?
+
+use Departures;
+
+/// How comfortable a transfer is, as minutes of slack between arriving at
+/// the connecting stop and the connecting departure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransferStatus {
+    /// at least `min_connection_minutes` of slack
+    Comfortable(i64),
+    /// a connection exists but with less slack than `min_connection_minutes`
+    Tight(i64),
+    /// no remaining departure at the connecting stop covers the estimated
+    /// arrival time
+    Missed,
+}
+
+/// The fixed parameters of a transfer: how long the first leg takes, and
+/// the minimum time you need at the connecting stop to make it (walking
+/// between platforms, etc.).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferPlan {
+    pub in_vehicle_minutes: i64,
+    pub min_connection_minutes: i64,
+}
+
+/// Check the next departure on `board_a` (optionally filtered by route)
+/// against `board_b`'s departures (optionally filtered by route), using
+/// `plan` to estimate the arrival time at the connecting stop.
+///
+/// Returns `None` if there's no (matching) departure on `board_a` at all.
+pub fn check_transfer(board_a: &Departures, route_a: Option<&str>, plan: &TransferPlan, board_b: &Departures, route_b: Option<&str>) -> Option<TransferStatus> {
+    let next_a = board_a
+        .departures
+        .iter()
+        .filter(|d| route_a.is_none_or(|r| d.route.eq_ignore_ascii_case(r)))
+        .min_by_key(|d| d.time)?;
+
+    let arrival_at_b = next_a.time + chrono::Duration::minutes(plan.in_vehicle_minutes);
+
+    let next_b = board_b
+        .departures
+        .iter()
+        .filter(|d| route_b.is_none_or(|r| d.route.eq_ignore_ascii_case(r)))
+        .filter(|d| d.time >= arrival_at_b)
+        .min_by_key(|d| d.time);
+
+    match next_b {
+        None => Some(TransferStatus::Missed),
+        Some(dep) => {
+            let slack = dep.time.signed_duration_since(arrival_at_b).num_minutes();
+            if slack < plan.min_connection_minutes {
+                Some(TransferStatus::Tight(slack))
+            } else {
+                Some(TransferStatus::Comfortable(slack))
+            }
+        }
+    }
+}
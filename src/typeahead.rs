@@ -0,0 +1,86 @@
+//! Search-as-you-type for stop names, built on [`KvvClient`] — the
+//! debounce-timer and stale-result bookkeeping every GUI autocomplete
+//! around this API ends up reinventing.
+//!
+//! This crate's HTTP layer is synchronous, so "cancelling" a superseded
+//! request works the same way as
+//! [`KvvClient::fetch_with_deadline`](::client::KvvClient::fetch_with_deadline):
+//! there's no future to drop, so an in-flight search keeps running to
+//! completion on its own thread regardless, its result just discarded if
+//! a newer query has since superseded it.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use client::{ClientError, KvvClient};
+use ranking::{self, RankedStop};
+
+/// How long to wait after the most recent keystroke before actually
+/// querying the API, so a fast typist's intermediate queries never hit
+/// the network.
+pub const DEFAULT_DEBOUNCE: StdDuration = StdDuration::from_millis(150);
+
+/// One incremental search session: feed it keystrokes via
+/// [`type_query`](Typeahead::type_query) and read ranked results off
+/// [`results`](Typeahead::results) as they arrive. Dropping the
+/// `Typeahead` ends its worker thread.
+pub struct Typeahead {
+    queries: mpsc::Sender<String>,
+    results: mpsc::Receiver<(String, Result<Vec<RankedStop>, ClientError>)>,
+}
+
+impl Typeahead {
+    /// A session against `client`, debouncing by [`DEFAULT_DEBOUNCE`].
+    pub fn new(client: KvvClient) -> Typeahead {
+        Typeahead::with_debounce(client, DEFAULT_DEBOUNCE)
+    }
+
+    /// Like [`new`](Typeahead::new), with an explicit debounce window.
+    pub fn with_debounce(client: KvvClient, debounce: StdDuration) -> Typeahead {
+        let (queries_tx, queries_rx) = mpsc::channel::<String>();
+        let (results_tx, results_rx) = mpsc::channel();
+        let generation = Arc::new(AtomicU64::new(0));
+
+        thread::spawn(move || {
+            while let Ok(first) = queries_rx.recv() {
+                // Coalesce every query that arrives within the debounce
+                // window into one, keeping only the last.
+                let mut query = first;
+                while let Ok(next) = queries_rx.recv_timeout(debounce) {
+                    query = next;
+                }
+
+                let my_generation = generation.fetch_add(1, Ordering::SeqCst) + 1;
+                let client = client.clone();
+                let generation = generation.clone();
+                let results_tx = results_tx.clone();
+                thread::spawn(move || {
+                    let result = client.search_by_name(&query).map(|stops| ranking::rank_by_name(&query, stops));
+                    // A newer query superseded this one while it was in
+                    // flight: drop the stale result instead of sending it.
+                    if generation.load(Ordering::SeqCst) == my_generation {
+                        let _ = results_tx.send((query, result));
+                    }
+                });
+            }
+        });
+
+        Typeahead { queries: queries_tx, results: results_rx }
+    }
+
+    /// Feed one keystroke's worth of query text into the session.
+    /// Superseded queries (including any already in flight) never reach
+    /// [`results`](Typeahead::results).
+    pub fn type_query(&self, query: &str) {
+        let _ = self.queries.send(query.to_owned());
+    }
+
+    /// Ranked results as they arrive, paired with the query text they
+    /// answer — useful for a GUI to double-check the text field hasn't
+    /// since changed again before displaying them.
+    pub fn results(&self) -> &mpsc::Receiver<(String, Result<Vec<RankedStop>, ClientError>)> {
+        &self.results
+    }
+}
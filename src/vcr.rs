@@ -0,0 +1,101 @@
+//! Recording live [`KvvClient`](::client::KvvClient) request/response
+//! pairs to a cassette file and replaying them from it, so integration
+//! tests (of downstream apps, and this crate's own) run hermetically
+//! instead of depending on the real API being reachable and returning
+//! stable data — while staying refreshable by re-recording against the
+//! live endpoint when the fixtures go stale.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One recorded request/response pair, keyed by the exact URL requested
+/// (including query parameters — callers usually compare on a
+/// normalized or at least API-key-free `KvvClient`, since two keys
+/// otherwise record as two distinct URLs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recording {
+    url: String,
+    body: String,
+}
+
+/// Whether a [`Cassette`] is serving previously recorded traffic or
+/// recording live traffic as it happens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Record,
+    Replay,
+}
+
+/// A VCR-style cassette: a JSON file of recorded request/response
+/// pairs, either replayed verbatim (erroring on any request that wasn't
+/// recorded) or extended with live traffic as it's made.
+pub struct Cassette {
+    path: PathBuf,
+    mode: Mode,
+    recordings: Mutex<HashMap<String, String>>,
+}
+
+fn load(path: &Path) -> io::Result<HashMap<String, String>> {
+    let text = fs::read_to_string(path)?;
+    let recordings: Vec<Recording> = serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(recordings.into_iter().map(|r| (r.url, r.body)).collect())
+}
+
+impl Cassette {
+    /// Load `path` (which must already exist) for replay: every request
+    /// made against a `KvvClient` using this cassette must match a URL
+    /// that was recorded into it, or the call fails.
+    pub fn open_for_replay<P: AsRef<Path>>(path: P) -> io::Result<Cassette> {
+        let path = path.as_ref().to_owned();
+        let recordings = load(&path)?;
+        Ok(Cassette { path, mode: Mode::Replay, recordings: Mutex::new(recordings) })
+    }
+
+    /// Start (or resume) recording to `path`: any existing recordings
+    /// are loaded first if the file exists, so re-recording only
+    /// overwrites the requests that are actually made again, leaving
+    /// the rest of the cassette untouched.
+    pub fn open_for_recording<P: AsRef<Path>>(path: P) -> io::Result<Cassette> {
+        let path = path.as_ref().to_owned();
+        let recordings = match load(&path) {
+            Ok(recordings) => recordings,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Cassette { path, mode: Mode::Record, recordings: Mutex::new(recordings) })
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// In [`Mode::Replay`], the previously recorded body for `url`, if
+    /// any. Always `None` in [`Mode::Record`] — a recording cassette
+    /// never shadows a live request, it only captures it afterwards via
+    /// [`record`](Cassette::record).
+    pub fn replay(&self, url: &str) -> Option<String> {
+        if self.mode == Mode::Replay {
+            self.recordings.lock().unwrap().get(url).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Add (or overwrite) a recording for `url` and persist the
+    /// cassette to disk immediately, so a crash mid-run doesn't lose
+    /// what was already captured.
+    pub fn record(&self, url: &str, body: &str) -> io::Result<()> {
+        self.recordings.lock().unwrap().insert(url.to_owned(), body.to_owned());
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let recordings = self.recordings.lock().unwrap();
+        let as_vec: Vec<Recording> = recordings.iter().map(|(url, body)| Recording { url: url.clone(), body: body.clone() }).collect();
+        let json = serde_json::to_string_pretty(&as_vec).expect("cassette recordings always serialize");
+        fs::write(&self.path, json)
+    }
+}
@@ -0,0 +1,87 @@
+//! Estimating where a vehicle currently is along its route from the live
+//! countdowns observed for the same trip at several of the stops it
+//! serves, for map displays that want to show a moving dot instead of
+//! just "next stop".
+//!
+//! This crate has no route shape (the actual road/rail geometry a
+//! vehicle follows between stops) to place a vehicle on — only stop
+//! coordinates — so [`interpolate`] draws a straight line between the
+//! two surrounding stops rather than snapping to the real track.
+
+use Stop;
+
+/// How long until a vehicle is expected at `stop`, as observed on a live
+/// board (e.g. read off a matching [`Departure::time`](::Departure::time)).
+/// Negative once the vehicle is overdue there, which this module treats
+/// as "already left".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StopEta<'a> {
+    pub stop: &'a Stop,
+    pub minutes_until: f64,
+}
+
+/// A vehicle's estimated current position, linearly interpolated between
+/// the two stops in its sequence that straddle now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VehiclePosition {
+    pub lat: f64,
+    pub lon: f64,
+    /// `0.0` at the earlier straddling stop, `1.0` at the later one.
+    pub progress: f64,
+}
+
+/// Estimate a vehicle's current position from ETAs observed at the stops
+/// along its route, given in stop order.
+///
+/// Finds the two consecutive stops whose ETAs straddle now — the vehicle
+/// already overdue at the first, not yet due at the second — and
+/// linearly interpolates between their coordinates by how far between
+/// the two ETAs now falls. Returns `None` if `etas` has fewer than two
+/// stops, or no consecutive pair straddles now (e.g. every observed ETA
+/// is still positive, meaning the vehicle hasn't left the first stop
+/// yet).
+pub fn interpolate(etas: &[StopEta]) -> Option<VehiclePosition> {
+    etas.windows(2).find_map(|pair| {
+        let (before, after) = (pair[0], pair[1]);
+        if before.minutes_until > 0.0 || after.minutes_until < 0.0 {
+            return None;
+        }
+
+        let span = after.minutes_until - before.minutes_until;
+        let progress = if span <= 0.0 { 0.0 } else { (-before.minutes_until / span).clamp(0.0, 1.0) };
+
+        Some(VehiclePosition {
+            lat: before.stop.lat + (after.stop.lat - before.stop.lat) * progress,
+            lon: before.stop.lon + (after.stop.lon - before.stop.lon) * progress,
+            progress,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Stop;
+
+    fn stop(lat: f64, lon: f64) -> Stop {
+        Stop { name: String::new(), id: "de:0:0".to_owned(), lat, lon }
+    }
+
+    #[test]
+    fn interpolates_between_straddling_stops() {
+        let a = stop(0.0, 0.0);
+        let b = stop(1.0, 1.0);
+        let etas = [StopEta { stop: &a, minutes_until: -2.0 }, StopEta { stop: &b, minutes_until: 2.0 }];
+        let pos = interpolate(&etas).unwrap();
+        assert_eq!(pos.progress, 0.5);
+        assert_eq!((pos.lat, pos.lon), (0.5, 0.5));
+    }
+
+    #[test]
+    fn no_position_when_nothing_straddles_now() {
+        let a = stop(0.0, 0.0);
+        let b = stop(1.0, 1.0);
+        let etas = [StopEta { stop: &a, minutes_until: 1.0 }, StopEta { stop: &b, minutes_until: 5.0 }];
+        assert_eq!(interpolate(&etas), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! Display-ready structures derived from [`Departures`], so GUI frameworks
+//! (egui, a web frontend, whatever comes next) don't each have to
+//! reimplement countdown formatting, line colors, and row identity for
+//! animating a refreshed board — see [`svg`](::svg) and
+//! [`term`](::term) for two front ends that already hand-rolled pieces of
+//! this before there was a shared place for it.
+
+use linemeta::Line;
+use {Departure, Departures, Occupancy};
+
+const DEFAULT_LINE_COLOR: &str = "#555555";
+
+/// One row of a departure board, pre-formatted for display.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DepartureRow {
+    /// Stable across refreshes of the same board as long as the same
+    /// vehicle is still listed — route, destination, and direction, the
+    /// same identity [`cancellation`](::cancellation) and
+    /// [`diff`](::diff) already match departures by. Use as a GUI
+    /// framework's animation/list key instead of the row index, so a
+    /// departure that moves up the list (closer to "now") animates as a
+    /// move rather than a delete-and-insert.
+    pub row_id: String,
+    /// tram line name
+    pub route: String,
+    /// official line color as a `#rrggbb` hex string, or a neutral gray
+    /// for a route [`linemeta`](::linemeta) doesn't know about
+    pub line_color: &'static str,
+    /// destination, with any via stops stripped — see [`Destination`](::Destination)
+    pub destination: String,
+    /// "now", "N min", or a clock time, depending on how far out it is;
+    /// a schedule-only clock time (no live tracking) is prefixed `~` —
+    /// see [`format_departure_time_annotated`](::format_departure_time_annotated)
+    pub countdown: String,
+    /// minutes until departure, negative if already due — for a GUI that
+    /// wants to sort or filter numerically instead of parsing `countdown`
+    pub minutes: i64,
+    /// real time data available for this departure?
+    pub realtime: bool,
+    /// low-floor (wheelchair-accessible) tram?
+    pub lowfloor: bool,
+    /// how full this vehicle is, if the backend reports it.
+    pub occupancy: Option<Occupancy>,
+    /// the last departure on the board served by a regular (non-night)
+    /// line — see [`Departures::last_before_night_service`] — so a board
+    /// can flag it as the last tram/bus before the night-service gap.
+    pub is_last_before_night_service: bool,
+}
+
+fn row_id(dep: &Departure) -> String {
+    format!("{}\u{0}{}\u{0}{}", dep.route, dep.destination.terminus, dep.direction)
+}
+
+impl DepartureRow {
+    fn from_departure(dep: &Departure, is_last_before_night_service: bool) -> DepartureRow {
+        DepartureRow {
+            row_id: row_id(dep),
+            route: dep.route.clone(),
+            line_color: Line::new(dep.route.clone()).metadata().map(|m| m.color).unwrap_or(DEFAULT_LINE_COLOR),
+            destination: dep.destination.terminus.clone(),
+            countdown: ::format_departure_time_annotated(dep.time, dep.realtime),
+            minutes: dep.time.signed_duration_since(chrono::Local::now()).num_minutes(),
+            realtime: dep.realtime,
+            lowfloor: dep.lowfloor,
+            occupancy: dep.occupancy,
+            is_last_before_night_service,
+        }
+    }
+}
+
+/// A display-ready departure board: a header and pre-formatted rows,
+/// already in the order the API returned them (soonest first).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardView {
+    /// human-readable stop name
+    pub stop_name: String,
+    /// when the underlying board was fetched, formatted `HH:MM`
+    pub as_of: String,
+    pub rows: Vec<DepartureRow>,
+}
+
+/// Build a [`BoardView`] from a freshly fetched board.
+pub fn view(board: &Departures) -> BoardView {
+    let last_before_night_service = board.last_before_night_service().map(row_id);
+    BoardView {
+        stop_name: board.stop_name.clone(),
+        as_of: board.timestamp.format("%H:%M").to_string(),
+        rows: board
+            .departures
+            .iter()
+            .map(|dep| DepartureRow::from_departure(dep, last_before_night_service.as_deref() == Some(row_id(dep).as_str())))
+            .collect(),
+    }
+}